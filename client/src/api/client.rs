@@ -1,106 +1,506 @@
-use gloo_net::{eventsource::futures::EventSource, http::Request};
+use crate::api::error::ApiError;
+use gloo::storage::{LocalStorage, Storage};
+use gloo_net::{
+    eventsource::futures::EventSource,
+    http::{Request, RequestBuilder, Response},
+};
 use once_cell::sync::OnceCell;
+use shared::types::availability::Availability;
+use shared::types::block::Block;
 use shared::types::block_response::BlockResponse;
 use shared::types::chain_config::ChainConfig;
+use shared::types::chain_result::ChainResult;
+use shared::types::chains_summary::ChainsSummary;
+use shared::types::health::Health;
+use shared::types::log_filter::LogFilter;
+use shared::types::log_lines::LogLinesResponse;
+use shared::types::log_search::LogSearchResponse;
+use shared::types::logs_response::LogsResponse;
+use shared::types::resource_usage::ResourceUsage;
 use shared::types::transaction_response::TransactionResponse;
 use std::sync::Arc;
 
 static INSTANCE: OnceCell<Arc<Api>> = OnceCell::new();
 
+const TOKEN_KEY: &str = "localchain_token";
+
+/// The server's message for a failed request, e.g. "chain 5 is not running"
+/// or "chain 5 not found", falling back to the status for empty bodies
+async fn error_message(resp: Response) -> ApiError {
+    let status = resp.status();
+    match resp.text().await {
+        Ok(text) if !text.trim().is_empty() => ApiError::Status(status, text),
+        _ => ApiError::Status(status, format!("HTTP {}", status)),
+    }
+}
+
 pub struct Api {
     base_url: String,
+    token: Option<String>,
 }
 
 impl Api {
     pub fn init(base_url: String) {
-        let _ = INSTANCE.set(Arc::new(Api { base_url }));
+        let token = Self::load_token();
+        let _ = INSTANCE.set(Arc::new(Api { base_url, token }));
     }
 
     pub fn instance() -> Arc<Self> {
         INSTANCE.get().unwrap().clone()
     }
 
-    pub async fn list_chains(&self) -> Result<Vec<ChainConfig>, String> {
-        let resp = Request::get(format!("{}/api/chains", self.base_url).as_str())
+    /// A `?token=` in the page URL wins and is remembered in localStorage
+    fn load_token() -> Option<String> {
+        let search = gloo::utils::window()
+            .location()
+            .search()
+            .unwrap_or_default();
+        let from_query = form_urlencoded::parse(search.trim_start_matches('?').as_bytes())
+            .find(|(key, _)| key == "token")
+            .map(|(_, token)| token.into_owned())
+            .filter(|t| !t.is_empty());
+        match from_query {
+            Some(token) => {
+                let _ = LocalStorage::set(TOKEN_KEY, &token);
+                Some(token)
+            }
+            None => LocalStorage::get(TOKEN_KEY).ok(),
+        }
+    }
+
+    fn get(&self, path: &str) -> RequestBuilder {
+        self.authorize(Request::get(format!("{}{}", self.base_url, path).as_str()))
+    }
+
+    fn post(&self, path: &str) -> RequestBuilder {
+        self.authorize(Request::post(format!("{}{}", self.base_url, path).as_str()))
+    }
+
+    fn authorize(&self, request: RequestBuilder) -> RequestBuilder {
+        match &self.token {
+            Some(token) => request.header("Authorization", &format!("Bearer {}", token)),
+            None => request,
+        }
+    }
+
+    /// `EventSource` can't send headers, so the token goes in the query
+    fn event_source(&self, path: &str) -> Result<EventSource, ApiError> {
+        let url = match &self.token {
+            Some(token) => format!(
+                "{}{}{}token={}",
+                self.base_url,
+                path,
+                if path.contains('?') { '&' } else { '?' },
+                js_sys::encode_uri_component(token)
+            ),
+            None => format!("{}{}", self.base_url, path),
+        };
+        EventSource::new(&url).map_err(|e| ApiError::Network(format!("{e:?}")))
+    }
+
+    pub async fn health(&self) -> Result<Health, ApiError> {
+        let resp = self
+            .get("/api/health")
+            .send()
+            .await
+            .map_err(ApiError::network)?;
+        if !resp.ok() {
+            return Err(error_message(resp).await);
+        }
+        resp.json().await.map_err(ApiError::decode)
+    }
+
+    pub async fn list_chains(&self) -> Result<Vec<ChainConfig>, ApiError> {
+        let resp = self
+            .get("/api/chains")
             .send()
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(ApiError::network)?;
         if !resp.ok() {
-            return Err(format!(""));
+            return Err(error_message(resp).await);
         }
-        resp.json().await.map_err(|e| e.to_string())
+        resp.json().await.map_err(ApiError::decode)
+    }
+
+    /// One entry of `list_chains`
+    pub async fn get_chain(&self, id: u64) -> Result<ChainConfig, ApiError> {
+        self.list_chains()
+            .await?
+            .into_iter()
+            .find(|c| c.id == id)
+            .ok_or_else(|| ApiError::Status(404, format!("chain {} not found", id)))
     }
 
-    pub async fn create_chain(&self, config: &ChainConfig) -> Result<ChainConfig, String> {
-        let resp = Request::post(format!("{}/api/chains", self.base_url).as_str())
+    pub async fn chains_summary(&self) -> Result<ChainsSummary, ApiError> {
+        let resp = self
+            .get("/api/chains?summary=true")
+            .send()
+            .await
+            .map_err(ApiError::network)?;
+        if !resp.ok() {
+            return Err(error_message(resp).await);
+        }
+        resp.json().await.map_err(ApiError::decode)
+    }
+
+    pub async fn create_chain(&self, config: &ChainConfig) -> Result<ChainConfig, ApiError> {
+        let resp = self
+            .post("/api/chains")
             .json(config)
-            .map_err(|e| e.to_string())?
+            .map_err(ApiError::decode)?
+            .send()
+            .await
+            .map_err(ApiError::network)?;
+        if !resp.ok() {
+            return Err(error_message(resp).await);
+        }
+        resp.json::<ChainConfig>().await.map_err(ApiError::decode)
+    }
+
+    pub async fn rename_chain(&self, chain_id: u64, name: &str) -> Result<ChainConfig, ApiError> {
+        let resp = self
+            .post(format!("/api/chains/{}/rename", chain_id).as_str())
+            .json(&serde_json::json!({ "name": name }))
+            .map_err(ApiError::decode)?
+            .send()
+            .await
+            .map_err(ApiError::network)?;
+        if !resp.ok() {
+            return Err(error_message(resp).await);
+        }
+        resp.json::<ChainConfig>().await.map_err(ApiError::decode)
+    }
+
+    pub async fn set_notes(&self, chain_id: u64, notes: &str) -> Result<ChainConfig, ApiError> {
+        let resp = self
+            .post(format!("/api/chains/{}/notes", chain_id).as_str())
+            .json(&serde_json::json!({ "notes": notes }))
+            .map_err(ApiError::decode)?
             .send()
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(ApiError::network)?;
         if !resp.ok() {
-            return Err(format!("HTTP {}", resp.status()));
+            return Err(error_message(resp).await);
         }
-        resp.json::<ChainConfig>().await.map_err(|e| e.to_string())
+        resp.json::<ChainConfig>().await.map_err(ApiError::decode)
     }
 
-    pub async fn post_action(&self, chain_id: &u64, action: &str) -> Result<(), String> {
-        let url = format!("{}/api/chains/{}/{}", self.base_url, chain_id, action);
-        let resp = Request::post(&url)
+    pub async fn set_block_time(&self, chain_id: u64, secs: u64) -> Result<ChainConfig, ApiError> {
+        let resp = self
+            .post(format!("/api/chains/{}/blocktime/{}", chain_id, secs).as_str())
             .send()
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(ApiError::network)?;
         if !resp.ok() {
-            return Err(format!("HTTP {}", resp.status()));
+            return Err(error_message(resp).await);
+        }
+        resp.json::<ChainConfig>().await.map_err(ApiError::decode)
+    }
+
+    pub async fn set_block_gas_limit(
+        &self,
+        chain_id: u64,
+        limit: u64,
+    ) -> Result<ChainConfig, ApiError> {
+        let resp = self
+            .post(format!("/api/chains/{}/gaslimit/{}", chain_id, limit).as_str())
+            .send()
+            .await
+            .map_err(ApiError::network)?;
+        if !resp.ok() {
+            return Err(error_message(resp).await);
+        }
+        resp.json::<ChainConfig>().await.map_err(ApiError::decode)
+    }
+
+    pub async fn export_chains(&self) -> Result<Vec<ChainConfig>, ApiError> {
+        let resp = self
+            .get("/api/chains/export-all")
+            .send()
+            .await
+            .map_err(ApiError::network)?;
+        if !resp.ok() {
+            return Err(error_message(resp).await);
+        }
+        resp.json().await.map_err(ApiError::decode)
+    }
+
+    pub async fn import_chains(
+        &self,
+        configs: &[ChainConfig],
+    ) -> Result<Vec<ChainResult>, ApiError> {
+        let resp = self
+            .post("/api/chains/import-all")
+            .json(configs)
+            .map_err(ApiError::decode)?
+            .send()
+            .await
+            .map_err(ApiError::network)?;
+        if !resp.ok() {
+            return Err(error_message(resp).await);
+        }
+        resp.json().await.map_err(ApiError::decode)
+    }
+
+    /// `start-all`, `stop-all` or `delete-all`, with one result per chain touched
+    pub async fn post_bulk_action(&self, action: &str) -> Result<Vec<ChainResult>, ApiError> {
+        let resp = self
+            .post(format!("/api/chains/{}", action).as_str())
+            .send()
+            .await
+            .map_err(ApiError::network)?;
+        if !resp.ok() {
+            return Err(error_message(resp).await);
+        }
+        resp.json().await.map_err(ApiError::decode)
+    }
+
+    pub async fn post_action(&self, chain_id: &u64, action: &str) -> Result<(), ApiError> {
+        let resp = self
+            .post(format!("/api/chains/{}/{}", chain_id, action).as_str())
+            .send()
+            .await
+            .map_err(ApiError::network)?;
+        if !resp.ok() {
+            return Err(error_message(resp).await);
         }
         Ok(())
     }
 
-    pub fn log_stream(&self, id: u64) -> Result<EventSource, String> {
-        let url = format!("/api/chains/{}/logstream", id);
-        EventSource::new(&url).map_err(|e| format!("{e:?}"))
+    pub async fn set_mining_paused(&self, chain_id: u64, paused: bool) -> Result<(), ApiError> {
+        self.post_action(&chain_id, &format!("mining/{}", paused))
+            .await
+    }
+
+    /// Mines one block, also while mining is paused
+    pub async fn mine(&self, chain_id: u64) -> Result<(), ApiError> {
+        self.post_action(&chain_id, "mine").await
     }
 
-    pub fn block_stream(&self, id: u64) -> Result<EventSource, String> {
-        let url = format!("/api/chains/{}/blockstream", id);
-        EventSource::new(&url).map_err(|e| format!("{e:?}"))
+    /// Replays the last `tail` buffered lines, or the whole buffer when `None`
+    pub fn log_stream(&self, id: u64, tail: Option<usize>) -> Result<EventSource, ApiError> {
+        let path = match tail {
+            Some(tail) => format!("/api/chains/{}/logstream?tail={}", id, tail),
+            None => format!("/api/chains/{}/logstream", id),
+        };
+        self.event_source(&path)
+    }
+
+    pub fn status_stream(&self, id: u64) -> Result<EventSource, ApiError> {
+        self.event_source(&format!("/api/chains/{}/statusstream", id))
+    }
+
+    /// Every chain created, changed or deleted, by this tab or any other
+    pub fn chain_events(&self) -> Result<EventSource, ApiError> {
+        self.event_source("/api/chains/events")
+    }
+
+    pub fn block_stream(&self, id: u64) -> Result<EventSource, ApiError> {
+        self.event_source(format!("/api/chains/{}/blockstream", id).as_str())
+    }
+
+    pub async fn availability(&self, id: u64, port: u16) -> Result<Availability, ApiError> {
+        let resp = self
+            .get(format!("/api/chains/available?id={}&port={}", id, port).as_str())
+            .send()
+            .await
+            .map_err(ApiError::network)?;
+        if !resp.ok() {
+            return Err(error_message(resp).await);
+        }
+        resp.json().await.map_err(ApiError::decode)
+    }
+
+    pub async fn chain_usage(&self, chain_id: u64) -> Result<ResourceUsage, ApiError> {
+        let resp = self
+            .get(format!("/api/chains/{}/usage", chain_id).as_str())
+            .send()
+            .await
+            .map_err(ApiError::network)?;
+        if !resp.ok() {
+            return Err(error_message(resp).await);
+        }
+        resp.json().await.map_err(ApiError::decode)
+    }
+
+    pub async fn latest_block_number(&self, chain_id: u64) -> Result<u64, ApiError> {
+        let resp = self
+            .get(format!("/api/chains/{}/head", chain_id).as_str())
+            .send()
+            .await
+            .map_err(ApiError::network)?;
+        if !resp.ok() {
+            return Err(error_message(resp).await);
+        }
+        resp.json().await.map_err(ApiError::decode)
+    }
+
+    /// Hash of a 1 wei transfer between two prefunded accounts
+    pub async fn send_test_transaction(&self, chain_id: u64) -> Result<String, ApiError> {
+        let resp = self
+            .post(format!("/api/chains/{}/testtx", chain_id).as_str())
+            .send()
+            .await
+            .map_err(ApiError::network)?;
+        if !resp.ok() {
+            return Err(error_message(resp).await);
+        }
+        resp.json().await.map_err(ApiError::decode)
+    }
+
+    /// The chain's log as plain text, the whole current file when the server
+    /// writes logs to disk
+    pub async fn download_logs(&self, chain_id: u64) -> Result<String, ApiError> {
+        let resp = self
+            .get(format!("/api/chains/{}/logs/download", chain_id).as_str())
+            .send()
+            .await
+            .map_err(ApiError::network)?;
+        if !resp.ok() {
+            return Err(error_message(resp).await);
+        }
+        resp.text().await.map_err(ApiError::decode)
+    }
+
+    /// Buffered log lines from `since` on, the whole buffer when `None`. The
+    /// polling counterpart of `log_stream`.
+    pub async fn logs_since(
+        &self,
+        chain_id: u64,
+        since: Option<u64>,
+    ) -> Result<LogLinesResponse, ApiError> {
+        let path = match since {
+            Some(since) => format!("/api/chains/{}/logs?since={}", chain_id, since),
+            None => format!("/api/chains/{}/logs", chain_id),
+        };
+        let resp = self.get(&path).send().await.map_err(ApiError::network)?;
+        if !resp.ok() {
+            return Err(error_message(resp).await);
+        }
+        resp.json().await.map_err(ApiError::decode)
+    }
+
+    pub async fn search_logs(
+        &self,
+        chain_id: u64,
+        query: &str,
+    ) -> Result<LogSearchResponse, ApiError> {
+        let resp = self
+            .get(
+                format!(
+                    "/api/chains/{}/logs/search?q={}",
+                    chain_id,
+                    js_sys::encode_uri_component(query)
+                )
+                .as_str(),
+            )
+            .send()
+            .await
+            .map_err(ApiError::network)?;
+        if !resp.ok() {
+            return Err(error_message(resp).await);
+        }
+        resp.json().await.map_err(ApiError::decode)
+    }
+
+    /// Pretty-printed `debug_traceTransaction` output
+    pub async fn trace_transaction(
+        &self,
+        chain_id: u64,
+        transaction_hash: &str,
+    ) -> Result<String, ApiError> {
+        let resp = self
+            .get(format!("/api/chains/{}/tx/{}/trace", chain_id, transaction_hash).as_str())
+            .send()
+            .await
+            .map_err(ApiError::network)?;
+        if !resp.ok() {
+            return Err(error_message(resp).await);
+        }
+        let trace: serde_json::Value = resp.json().await.map_err(ApiError::decode)?;
+        serde_json::to_string_pretty(&trace).map_err(ApiError::decode)
+    }
+
+    pub async fn get_blocks_range(
+        &self,
+        chain_id: u64,
+        from: u64,
+        to: u64,
+    ) -> Result<Vec<Block>, ApiError> {
+        let resp = self
+            .get(
+                format!(
+                    "/api/chains/{}/blocks/range?from={}&to={}",
+                    chain_id, from, to
+                )
+                .as_str(),
+            )
+            .send()
+            .await
+            .map_err(ApiError::network)?;
+        if !resp.ok() {
+            return Err(error_message(resp).await);
+        }
+        resp.json().await.map_err(ApiError::decode)
     }
 
     pub async fn get_block(
         &self,
         chain_id: u64,
         block_number: u64,
-    ) -> Result<BlockResponse, String> {
-        let resp = Request::get(
-            format!("{}/api/{}/blocks/{}", self.base_url, chain_id, block_number).as_str(),
-        )
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
+    ) -> Result<BlockResponse, ApiError> {
+        let resp = self
+            .get(format!("/api/{}/blocks/{}", chain_id, block_number).as_str())
+            .send()
+            .await
+            .map_err(ApiError::network)?;
+        if !resp.ok() {
+            return Err(error_message(resp).await);
+        }
+        resp.json().await.map_err(ApiError::decode)
+    }
+
+    pub async fn get_genesis(&self, chain_id: u64) -> Result<BlockResponse, ApiError> {
+        let resp = self
+            .get(format!("/api/chains/{}/genesis", chain_id).as_str())
+            .send()
+            .await
+            .map_err(ApiError::network)?;
         if !resp.ok() {
-            return Err(format!("HTTP {}", resp.status()));
+            return Err(error_message(resp).await);
         }
-        resp.json().await.map_err(|e| e.to_string())
+        resp.json().await.map_err(ApiError::decode)
     }
 
     pub async fn get_transaction(
         &self,
         chain_id: u64,
         transaction_hash: String,
-    ) -> Result<TransactionResponse, String> {
-        let resp = Request::get(
-            format!(
-                "{}/api/{}/transactions/{}",
-                self.base_url, chain_id, transaction_hash
-            )
-            .as_str(),
-        )
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
+    ) -> Result<TransactionResponse, ApiError> {
+        let resp = self
+            .get(format!("/api/{}/transactions/{}", chain_id, transaction_hash).as_str())
+            .send()
+            .await
+            .map_err(ApiError::network)?;
+        if !resp.ok() {
+            return Err(error_message(resp).await);
+        }
+        resp.json().await.map_err(ApiError::decode)
+    }
+
+    pub async fn get_logs(
+        &self,
+        chain_id: u64,
+        filter: &LogFilter,
+    ) -> Result<LogsResponse, ApiError> {
+        let resp = self
+            .get(format!("/api/{}/events?{}", chain_id, filter.to_query()).as_str())
+            .send()
+            .await
+            .map_err(ApiError::network)?;
         if !resp.ok() {
-            return Err(format!("HTTP {}", resp.status()));
+            return Err(error_message(resp).await);
         }
-        resp.json().await.map_err(|e| e.to_string())
+        resp.json().await.map_err(ApiError::decode)
     }
 }
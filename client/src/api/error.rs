@@ -0,0 +1,34 @@
+use std::fmt;
+
+/// Why an `Api` call failed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiError {
+    /// No answer from the server, e.g. it is down or restarting
+    Network(String),
+    /// The server answered with a non-2xx status and this message
+    Status(u16, String),
+    /// The request body couldn't be encoded or the response wasn't the
+    /// expected shape
+    Decode(String),
+}
+
+impl ApiError {
+    pub(crate) fn network(e: impl fmt::Display) -> Self {
+        ApiError::Network(e.to_string())
+    }
+
+    pub(crate) fn decode(e: impl fmt::Display) -> Self {
+        ApiError::Decode(e.to_string())
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiError::Network(e) => write!(f, "Server unreachable: {}", e),
+            // the server's messages are written to be shown as is
+            ApiError::Status(_, message) => f.write_str(message),
+            ApiError::Decode(e) => write!(f, "Unexpected response: {}", e),
+        }
+    }
+}
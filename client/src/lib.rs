@@ -1,19 +1,52 @@
 use crate::api::client::Api;
+use crate::api::error::ApiError;
 use crate::ui::block_page::BlockPage;
-use crate::ui::blocks_column::BlocksColumn;
-use crate::ui::logs_column::LogsColumn;
+use crate::ui::blocks_column::{format_timestamp, merge_blocks, BlocksColumn};
+use crate::ui::compare_page::ComparePage;
+use crate::ui::dashboard_page::DashboardPage;
+use crate::ui::download::download_file;
+use crate::ui::events_page::EventsPage;
+use crate::ui::fork_bookmarks::ForkBookmarks;
+use crate::ui::gas_sparkline::GasSparkline;
+use crate::ui::logs_column::{follow_logs, LogsColumn};
+use crate::ui::logs_page::LogsPage;
 use crate::ui::transaction_page::TransactionPage;
 use futures_util::{pin_mut, StreamExt};
+use gloo::storage::{LocalStorage, Storage};
 use leptos::task::spawn_local;
 use leptos::{leptos_dom::logging::console_error, prelude::*};
 use leptos_router::{components::*, path};
+use shared::types::availability::Availability;
 use shared::types::block::Block;
-use shared::types::chain_config::{ChainConfig, ChainStatus};
+use shared::types::chain_config::{
+    join_args, split_args, ChainConfig, ChainStatus, Runtime, AUTO_PORT, DEFAULT_DECIMALS,
+    DEFAULT_HOST, DEFAULT_SYMBOL, MIN_BLOCK_GAS_LIMIT,
+};
+use shared::types::chain_event::{ChainEvent, ChainEventKind};
+use shared::types::chain_status_event::ChainStatusEvent;
+use shared::types::resource_usage::ResourceUsage;
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
+use std::time::Duration;
 
 mod api;
 mod ui;
 
+/// Buffered log lines replayed when a column subscribes
+const LOG_TAIL: usize = 200;
+
+/// Blocks loaded into a running chain's column before the stream takes over
+const RECENT_BLOCKS: u64 = 20;
+
+/// localStorage key remembering whether the modal's Advanced section is open
+const ADVANCED_KEY: &str = "localchain_modal_advanced";
+
+/// Number of recent blocks the production rate is averaged over
+const RATE_WINDOW: usize = 10;
+
+/// Wait between chain list requests while the server is unreachable
+const REFRESH_RETRY: Duration = Duration::from_secs(2);
+
 #[component]
 pub fn HomePage() -> impl IntoView {
     let (show_modal, set_show_modal) = signal(false);
@@ -21,16 +54,66 @@ pub fn HomePage() -> impl IntoView {
     let (chains, set_chains) = signal::<Vec<ChainConfig>>(vec![]);
     let (loading, set_loading) = signal(false);
     let (error_msg, set_error_msg) = signal::<Option<String>>(None);
+    let (notice, set_notice) = signal::<Option<String>>(None);
+    let (read_only, set_read_only) = signal(false);
+    let (allow_privileged_ports, set_allow_privileged_ports) = signal(false);
+    let (max_chains, set_max_chains) = signal::<Option<usize>>(None);
+    let (tag_filter, set_tag_filter) = signal::<Option<String>>(None);
+    let (search, set_search) = signal(String::new());
+    // chain id -> action awaiting a response, guards against double clicks
+    let (in_flight, set_in_flight) = signal(HashMap::<u64, &'static str>::new());
+
+    // the limit, once that many chains exist
+    let chain_limit = Signal::derive(move || {
+        max_chains
+            .get()
+            .filter(|max| chains.with(|c| c.len() >= *max))
+    });
+    let all_tags = Signal::derive(move || {
+        let mut tags: Vec<String> = chains.get().into_iter().flat_map(|c| c.tags).collect();
+        tags.sort();
+        tags.dedup();
+        tags
+    });
+
+    let visible_chains = Signal::derive(move || {
+        let tag = tag_filter.get();
+        let query = search.get().trim().to_lowercase();
+        let mut chains: Vec<ChainConfig> = chains
+            .get()
+            .into_iter()
+            .filter(|c| tag.as_ref().is_none_or(|t| c.tags.contains(t)))
+            .filter(|c| {
+                query.is_empty()
+                    || c.name.to_lowercase().contains(&query)
+                    || c.id.to_string() == query
+                    || c.port.to_string() == query
+            })
+            .collect();
+        chains.sort_by_key(|c| (!c.pinned, c.created_seq));
+        chains
+    });
 
     let refresh = move || {
         set_loading.set(true);
         set_error_msg.set(None);
         spawn_local(async move {
-            match Api::instance().list_chains().await {
-                Ok(list) => {
-                    set_chains.set(list);
+            // keep polling while the server is down or restarting, any
+            // answer from it, even an error, ends the loop
+            loop {
+                match Api::instance().list_chains().await {
+                    Ok(list) => {
+                        set_chains.set(list);
+                        set_error_msg.set(None);
+                    }
+                    Err(ApiError::Network(_)) => {
+                        set_error_msg.set(Some("Server unreachable, retrying...".to_string()));
+                        gloo::timers::future::sleep(REFRESH_RETRY).await;
+                        continue;
+                    }
+                    Err(e) => set_error_msg.set(Some(e.to_string())),
                 }
-                Err(e) => set_error_msg.set(Some(e)),
+                break;
             }
             set_loading.set(false);
         });
@@ -38,26 +121,228 @@ pub fn HomePage() -> impl IntoView {
 
     // run once on mount
     Effect::new(move |_| refresh());
+    Effect::new(move |_| {
+        spawn_local(async move {
+            if let Ok(health) = Api::instance().health().await {
+                set_read_only.set(health.read_only);
+                set_allow_privileged_ports.set(health.allow_privileged_ports);
+                set_max_chains.set(health.max_chains);
+            }
+        });
+    });
+
+    // changes made in other tabs, or by scripts against the API. Applied by
+    // id, so this tab's own creates and deletes, which also refresh the
+    // list, land once.
+    Effect::new(move |_| {
+        spawn_local(async move {
+            match Api::instance().chain_events() {
+                Ok(mut es) => {
+                    let events = es.subscribe("message").unwrap();
+                    pin_mut!(events);
+
+                    while let Some(Ok((_event_type, msg))) = events.next().await {
+                        let Some(Ok(event)) = msg
+                            .data()
+                            .as_string()
+                            .map(|data| serde_json::from_str::<ChainEvent>(&data))
+                        else {
+                            console_error(format!("Error reading chain event: {:?}", msg).as_ref());
+                            continue;
+                        };
+                        set_chains.update(|chains| {
+                            let position = chains.iter().position(|c| c.id == event.chain.id);
+                            match (event.kind, position) {
+                                (ChainEventKind::Deleted, Some(i)) => {
+                                    chains.remove(i);
+                                }
+                                (ChainEventKind::Deleted, None) => {}
+                                (_, Some(i)) => chains[i] = event.chain,
+                                (_, None) => chains.push(event.chain),
+                            }
+                        });
+                    }
+                }
+                Err(e) => {
+                    console_error(format!("Error reading SSE message: {:?}", e).as_ref());
+                }
+            }
+        });
+    });
 
-    let on_created = {
-        let refresh = refresh.clone();
-        move |_id: u64| refresh()
+    let on_created = move |_id: u64| refresh();
+
+    let on_export: Rc<dyn Fn()> = Rc::new(move || {
+        set_error_msg.set(None);
+        spawn_local(async move {
+            match Api::instance().export_chains().await {
+                Ok(list) => match serde_json::to_string_pretty(&list) {
+                    Ok(json) => download_file("localchain-chains.json", "application/json", &json),
+                    Err(e) => set_error_msg.set(Some(e.to_string())),
+                },
+                Err(e) => set_error_msg.set(Some(e.to_string())),
+            }
+        });
+    });
+
+    let on_import: Rc<dyn Fn(web_sys::File)> = Rc::new(move |file| {
+        set_error_msg.set(None);
+        set_notice.set(None);
+        spawn_local(async move {
+            let file = gloo::file::File::from(file);
+            let configs = match gloo::file::futures::read_as_text(&file).await {
+                Ok(text) => serde_json::from_str::<Vec<ChainConfig>>(&text),
+                Err(e) => {
+                    set_error_msg.set(Some(e.to_string()));
+                    return;
+                }
+            };
+            let configs = match configs {
+                Ok(configs) => configs,
+                Err(e) => {
+                    set_error_msg.set(Some(format!("Invalid chains file: {}", e)));
+                    return;
+                }
+            };
+            match Api::instance().import_chains(&configs).await {
+                Ok(results) => {
+                    let imported = results.iter().filter(|r| r.ok).count();
+                    let skipped: Vec<String> = results
+                        .iter()
+                        .filter(|r| !r.ok)
+                        .map(|r| format!("{} ({})", r.name, r.error.clone().unwrap_or_default()))
+                        .collect();
+                    set_notice.set(Some(if skipped.is_empty() {
+                        format!("Imported {} chains", imported)
+                    } else {
+                        format!(
+                            "Imported {} chains, skipped {}: {}",
+                            imported,
+                            skipped.len(),
+                            skipped.join(", ")
+                        )
+                    }));
+                }
+                Err(e) => set_error_msg.set(Some(e.to_string())),
+            }
+            refresh();
+        });
+    });
+
+    let on_bulk: Rc<dyn Fn(&'static str)> = Rc::new(move |action| {
+        set_error_msg.set(None);
+        set_notice.set(None);
+        spawn_local(async move {
+            match Api::instance().post_bulk_action(action).await {
+                Ok(results) => {
+                    let verb = match action {
+                        "start-all" => "started",
+                        "stop-all" => "stopped",
+                        _ => "deleted",
+                    };
+                    let done = results.iter().filter(|r| r.ok).count();
+                    let failed: Vec<String> = results
+                        .iter()
+                        .filter(|r| !r.ok)
+                        .map(|r| format!("{}: {}", r.name, r.error.clone().unwrap_or_default()))
+                        .collect();
+                    set_notice.set(Some(if failed.is_empty() {
+                        format!("{} {}", done, verb)
+                    } else {
+                        format!(
+                            "{} {}, {} failed ({})",
+                            done,
+                            verb,
+                            failed.len(),
+                            failed.join(", ")
+                        )
+                    }));
+                }
+                Err(e) => set_error_msg.set(Some(e.to_string())),
+            }
+            refresh();
+        });
+    });
+
+    // typing the word keeps a stray click from wiping every chain
+    let on_delete_all: Rc<dyn Fn()> = {
+        let on_bulk = on_bulk.clone();
+        Rc::new(move || {
+            let names: Vec<String> = chains
+                .get_untracked()
+                .iter()
+                .map(|c| format!("- {} (id {}, port {})", c.name, c.id, c.port))
+                .collect();
+            if names.is_empty() {
+                return;
+            }
+            let message = format!(
+                "This stops and deletes {} chains:\n{}\n\nType \"delete\" to confirm.",
+                names.len(),
+                names.join("\n")
+            );
+            let answer = window().prompt_with_message(&message).ok().flatten();
+            if answer.is_some_and(|a| a.trim() == "delete") {
+                on_bulk("delete-all");
+            }
+        })
     };
+    // bumped by "Clear All Logs", every column empties its log pane
+    let (clear_logs, set_clear_logs) = signal(0u64);
+    let on_clear_logs: Rc<dyn Fn()> = Rc::new(move || set_clear_logs.update(|n| *n += 1));
 
     let on_action = move |id: u64, action: &'static str| {
+        if in_flight.get_untracked().contains_key(&id) {
+            return;
+        }
+        set_in_flight.update(|m| {
+            m.insert(id, action);
+        });
         set_error_msg.set(None);
         spawn_local(async move {
             if let Err(e) = Api::instance().post_action(&id, action).await {
-                set_error_msg.set(Some(e));
+                set_error_msg.set(Some(e.to_string()));
             }
             // always refresh to reflect latest server state
-            refresh();
+            match Api::instance().list_chains().await {
+                Ok(list) => set_chains.set(list),
+                Err(e) => set_error_msg.set(Some(e.to_string())),
+            }
+            set_in_flight.update(|m| {
+                m.remove(&id);
+            });
         });
     };
 
     view! {
         <main style="font-family: system-ui, -apple-system, Segoe UI, Roboto, Ubuntu, Cantarell, Noto Sans, Helvetica, Arial, Apple Color Emoji, Segoe UI Emoji;">
-            <TopBar set_show_modal=set_show_modal set_modal_config=set_modal_config />
+            <TopBar
+                set_show_modal=set_show_modal
+                set_modal_config=set_modal_config
+                read_only=read_only
+                chain_limit=chain_limit
+                tags=all_tags
+                tag_filter=tag_filter
+                set_tag_filter=set_tag_filter
+                search=search
+                set_search=set_search
+                on_export=on_export
+                on_import=on_import
+                on_bulk=on_bulk
+                on_delete_all=on_delete_all
+                on_clear_logs=on_clear_logs
+            />
+            {move || {
+                notice
+                    .get()
+                    .map(|n| {
+                        view! {
+                            <div style="margin:8px; padding:8px; color:#084298; background:#cfe2ff; border:1px solid #b6d4fe; border-radius:6px;">
+                                {n}
+                            </div>
+                        }
+                    })
+            }}
             {move || {
                 error_msg
                     .get()
@@ -76,17 +361,70 @@ pub fn HomePage() -> impl IntoView {
                     None
                 }
             }}
+            {move || {
+                (!chains.get().is_empty() && visible_chains.get().is_empty())
+                    .then(|| {
+                        view! {
+                            <div style="margin:16px; color:#6b7280;">{"No chains match"}</div>
+                        }
+                    })
+            }}
             <div style="display:flex; gap:16px; overflow-x:auto; padding:16px;">
                 <For
-                    each=move || chains.get()
-                    key=|c| c.name.clone()
+                    each=move || visible_chains.get()
+                    key=|c| c.id
                     children=move |c: ChainConfig| {
                         let id = c.id;
+                        let initial_status = c.status;
                         let cb: Rc<dyn Fn(&'static str)> = Rc::new(move |action| on_action(
                             id,
                             action,
                         ));
-                        view! { <ChainColumn chain=c on_action=cb.clone() /> }
+                        let status = Signal::derive(move || {
+                            chains
+                                .get()
+                                .iter()
+                                .find(|c| c.id == id)
+                                .map(|c| c.status)
+                                .unwrap_or(initial_status)
+                        });
+                        let pending = Signal::derive(move || in_flight.get().get(&id).copied());
+                        let actual_chain_id = Signal::derive(move || {
+                            chains
+                                .get()
+                                .iter()
+                                .find(|c| c.id == id)
+                                .and_then(|c| c.actual_chain_id)
+                        });
+                        let resolved_fork_block = Signal::derive(move || {
+                            chains
+                                .get()
+                                .iter()
+                                .find(|c| c.id == id)
+                                .and_then(|c| c.resolved_fork_block)
+                        });
+                        let initial_pinned = c.pinned;
+                        let pinned = Signal::derive(move || {
+                            chains
+                                .get()
+                                .iter()
+                                .find(|c| c.id == id)
+                                .map(|c| c.pinned)
+                                .unwrap_or(initial_pinned)
+                        });
+                        view! {
+                            <ChainColumn
+                                chain=c
+                                status=status
+                                actual_chain_id=actual_chain_id
+                                resolved_fork_block=resolved_fork_block
+                                pinned=pinned
+                                pending=pending
+                                on_action=cb.clone()
+                                read_only=read_only
+                                clear_logs=clear_logs
+                            />
+                        }
                     }
                 />
             </div>
@@ -96,16 +434,14 @@ pub fn HomePage() -> impl IntoView {
                     .get()
                     .then(|| {
                         let existing = chains.get().clone();
-                        let on_close = {
-                            let set_show_modal = set_show_modal.clone();
-                            Rc::new(move || set_show_modal.set(false))
-                        };
-                        let on_created: Rc<dyn Fn(u64)> = Rc::new(move |id| on_created(id));
+                        let on_close = Rc::new(move || set_show_modal.set(false));
+                        let on_created: Rc<dyn Fn(u64)> = Rc::new(on_created);
                         let config = modal_config.get();
                         view! {
                             <NewChainModal
                                 config=config
                                 existing_chains=existing
+                                allow_privileged_ports=allow_privileged_ports.get_untracked()
                                 on_close=on_close
                                 on_created=on_created
                             />
@@ -125,10 +461,17 @@ pub fn main() {
             <Router>
                 <Routes fallback=|| view! { <div>"Not found"</div> }>
                     <Route path=path!("") view=move || view! { <HomePage /> } />
+                    <Route path=path!("dashboard") view=move || view! { <DashboardPage /> } />
                     <Route
                         path=path!(":chainid/transactions/:transactionhash")
                         view=move || view! { <TransactionPage /> }
                     />
+                    <Route path=path!(":chainid/events") view=move || view! { <EventsPage /> } />
+                    <Route path=path!(":chainid/logs") view=move || view! { <LogsPage /> } />
+                    <Route
+                        path=path!(":chainid/compare/:a/:b")
+                        view=move || view! { <ComparePage /> }
+                    />
                     <Route
                         path=path!(":chainid/:blocknumber")
                         view=move || view! { <BlockPage /> }
@@ -145,43 +488,158 @@ pub fn main() {
 fn TopBar(
     set_show_modal: WriteSignal<bool>,
     set_modal_config: WriteSignal<Option<ChainConfig>>,
+    read_only: ReadSignal<bool>,
+    /// Set to the server's chain limit once it is reached
+    chain_limit: Signal<Option<usize>>,
+    tags: Signal<Vec<String>>,
+    tag_filter: ReadSignal<Option<String>>,
+    set_tag_filter: WriteSignal<Option<String>>,
+    search: ReadSignal<String>,
+    set_search: WriteSignal<String>,
+    on_export: Rc<dyn Fn()>,
+    on_import: Rc<dyn Fn(web_sys::File)>,
+    /// `start-all` or `stop-all`
+    on_bulk: Rc<dyn Fn(&'static str)>,
+    /// Confirms, then sends `delete-all`
+    on_delete_all: Rc<dyn Fn()>,
+    on_clear_logs: Rc<dyn Fn()>,
 ) -> impl IntoView {
+    let on_start_all = on_bulk.clone();
+    let on_stop_all = on_bulk;
     view! {
         <div style="display:flex; align-items:center; justify-content:space-between; padding:12px 16px; border-bottom:1px solid #e5e7eb; position:sticky; top:0; background:#fff; z-index:10;">
-            <div style="font-weight:600; font-size:18px;">{"Local Chain"}</div>
-            <div style="display:flex; gap:8px;">
-                <button
-                    on:click=move |_| {
-                        set_modal_config
-                            .set(
-                                Some(ChainConfig {
-                                    name: "Ethereum".to_string(),
-                                    id: 1,
-                                    port: 8545,
-                                    block_time: 1,
-                                    status: ChainStatus::Stopped,
-                                    fork_url: None,
-                                }),
-                            );
-                        set_show_modal.set(true);
+            <div style="display:flex; align-items:center; gap:16px;">
+                <div style="font-weight:600; font-size:18px;">{"Local Chain"}</div>
+                <input
+                    prop:value=move || search.get()
+                    on:input=move |ev| set_search.set(event_target_value(&ev))
+                    placeholder="Search name, id or port"
+                    style="padding:6px; border:1px solid #e5e7eb; border-radius:6px; width:220px;"
+                />
+                <select
+                    prop:value=move || tag_filter.get().unwrap_or_default()
+                    on:change=move |ev| {
+                        let tag = event_target_value(&ev);
+                        set_tag_filter.set(Some(tag).filter(|t| !t.is_empty()));
                     }
-                    style="background:none; border:none; padding:8px; border-radius:6px; cursor:pointer;"
+                    style="padding:6px; border:1px solid #e5e7eb; border-radius:6px; background:white;"
                 >
-                    <img
-                        src="/assets/ethereum_logo.svg"
-                        alt="New Ethereum Chain"
-                        style="width:32px; height:32px;"
+                    <option value="">{"All tags"}</option>
+                    <For
+                        each=move || tags.get()
+                        key=|tag| tag.clone()
+                        children=move |tag: String| {
+                            view! { <option value=tag.clone()>{tag.clone()}</option> }
+                        }
                     />
+                </select>
+            </div>
+            <div style="display:flex; gap:8px; align-items:center;">
+                <A href="/dashboard">
+                    <span style="display:inline-block; color:inherit; background:white; border:1px solid #d1d5db; padding:8px 12px; border-radius:6px; cursor:pointer;">
+                        {"Dashboard"}
+                    </span>
+                </A>
+                <button
+                    on:click=move |_| on_export()
+                    style="background:white; border:1px solid #d1d5db; padding:8px 12px; border-radius:6px; cursor:pointer;"
+                >
+                    {"Export All"}
                 </button>
                 <button
-                    on:click=move |_| {
-                        set_modal_config.set(None);
-                        set_show_modal.set(true);
-                    }
-                    style="background:#2563eb; color:white; border:none; padding:8px 12px; border-radius:6px; cursor:pointer;"
+                    on:click=move |_| on_clear_logs()
+                    style="background:white; border:1px solid #d1d5db; padding:8px 12px; border-radius:6px; cursor:pointer;"
                 >
-                    {"New Chain"}
+                    {"Clear All Logs"}
                 </button>
+                <label style=move || {
+                    format!(
+                        "display:{}; background:white; border:1px solid #d1d5db; padding:8px 12px; border-radius:6px; cursor:pointer;",
+                        if read_only.get() { "none" } else { "inline-block" },
+                    )
+                }>
+                    {"Import All"}
+                    <input
+                        type="file"
+                        accept="application/json"
+                        style="display:none;"
+                        on:change=move |ev| {
+                            let input = event_target::<web_sys::HtmlInputElement>(&ev);
+                            if let Some(file) = input.files().and_then(|files| files.get(0)) {
+                                on_import(file);
+                            }
+                            input.set_value("");
+                        }
+                    />
+                </label>
+                <div style=move || {
+                    format!("display:{}; gap:8px;", if read_only.get() { "none" } else { "flex" })
+                }>
+                    <button
+                        on:click=move |_| on_start_all("start-all")
+                        style="background:white; border:1px solid #d1d5db; padding:8px 12px; border-radius:6px; cursor:pointer;"
+                    >
+                        {"Start All"}
+                    </button>
+                    <button
+                        on:click=move |_| on_stop_all("stop-all")
+                        style="background:white; border:1px solid #d1d5db; padding:8px 12px; border-radius:6px; cursor:pointer;"
+                    >
+                        {"Stop All"}
+                    </button>
+                    <button
+                        on:click=move |_| on_delete_all()
+                        style="background:white; color:#b91c1c; border:1px solid #fca5a5; padding:8px 12px; border-radius:6px; cursor:pointer;"
+                    >
+                        {"Delete All Chains"}
+                    </button>
+                    {move || {
+                        chain_limit
+                            .get()
+                            .map(|max| {
+                                view! {
+                                    <span style="color:#6b7280; font-size:13px;">
+                                        {format!("Limit of {} chains reached", max)}
+                                    </span>
+                                }
+                            })
+                    }}
+                    <button
+                        disabled=move || chain_limit.get().is_some()
+                        on:click=move |_| {
+                            set_modal_config
+                                .set(
+                                    Some(ChainConfig {
+                                        name: "Ethereum".to_string(),
+                                        id: 1,
+                                        port: 8545,
+                                        block_time: 1,
+                                        status: ChainStatus::Stopped,
+                                        fork_url: None,
+                                        ..Default::default()
+                                    }),
+                                );
+                            set_show_modal.set(true);
+                        }
+                        style="background:none; border:none; padding:8px; border-radius:6px; cursor:pointer;"
+                    >
+                        <img
+                            src="/assets/ethereum_logo.svg"
+                            alt="New Ethereum Chain"
+                            style="width:32px; height:32px;"
+                        />
+                    </button>
+                    <button
+                        disabled=move || chain_limit.get().is_some()
+                        on:click=move |_| {
+                            set_modal_config.set(None);
+                            set_show_modal.set(true);
+                        }
+                        style="background:#2563eb; color:white; border:none; padding:8px 12px; border-radius:6px; cursor:pointer;"
+                    >
+                        {"New Chain"}
+                    </button>
+                </div>
             </div>
         </div>
     }
@@ -191,6 +649,7 @@ fn TopBar(
 fn NewChainModal(
     config: Option<ChainConfig>,
     existing_chains: Vec<ChainConfig>,
+    allow_privileged_ports: bool,
     on_close: Rc<dyn Fn()>,
     on_created: Rc<dyn Fn(u64)>,
 ) -> impl IntoView {
@@ -201,27 +660,146 @@ fn NewChainModal(
     let (name, set_name) = signal(config.name.clone());
     let (chain_id, set_chain_id) = signal(config.id.to_string());
     let (port, set_port) = signal(config.port.to_string());
+    let (auto_port, set_auto_port) = signal(config.port == AUTO_PORT);
     let (block_time, set_block_time) = signal(config.block_time.to_string());
-    let(fork_url, set_fork_url) = signal(config.fork_url.clone());
+    let (fork_url, set_fork_url) = signal(config.fork_url.clone());
+    let (fork_block, set_fork_block) = signal(
+        config
+            .fork_block_number
+            .map(|n| n.to_string())
+            .unwrap_or_default(),
+    );
+    let (pin_fork_block, set_pin_fork_block) = signal(config.pin_fork_block);
+    let (color, set_color) = signal(config.color.clone().unwrap_or_default());
+    let (tags, set_tags) = signal(config.tags.join(", "));
+    let (notes, set_notes) = signal(config.notes.clone().unwrap_or_default());
+    let (symbol, set_symbol) = signal(config.symbol.clone().unwrap_or_default());
+    let (decimals, set_decimals) =
+        signal(config.decimals.map(|d| d.to_string()).unwrap_or_default());
+    let (tracing, set_tracing) = signal(config.tracing);
+    let (docker, set_docker) = signal(config.runtime() == Runtime::Docker);
+    let (record_transactions, set_record_transactions) = signal(config.record_transactions);
+    let (auto_start, set_auto_start) = signal(config.auto_start);
+    // a prefilled fork URL shouldn't be hidden behind a collapsed section
+    let (show_advanced, set_show_advanced) = signal(
+        LocalStorage::get::<bool>(ADVANCED_KEY).unwrap_or(false) || config.fork_url.is_some(),
+    );
+    let (host, set_host) = signal(config.host.clone().unwrap_or_default());
+    let (genesis_timestamp, set_genesis_timestamp) = signal(
+        config
+            .genesis_timestamp
+            .map(|t| t.to_string())
+            .unwrap_or_default(),
+    );
+    let (gas_price, set_gas_price) =
+        signal(config.gas_price.map(|p| p.to_string()).unwrap_or_default());
+    let (block_gas_limit, set_block_gas_limit) = signal(
+        config
+            .block_gas_limit
+            .map(|l| l.to_string())
+            .unwrap_or_default(),
+    );
+    let (premine, set_premine) = signal(
+        config
+            .premine_blocks
+            .map(|n| n.to_string())
+            .unwrap_or_default(),
+    );
+    let (log_retention, set_log_retention) = signal(
+        config
+            .log_retention
+            .map(|n| n.to_string())
+            .unwrap_or_default(),
+    );
+    let (heartbeat, set_heartbeat) = signal(
+        config
+            .heartbeat_secs
+            .map(|s| s.to_string())
+            .unwrap_or_default(),
+    );
+    let (extra_args, set_extra_args) = signal(join_args(&config.extra_args));
+    let (env, set_env) = signal({
+        let mut env: Vec<(String, String)> = config.env.clone().into_iter().collect();
+        env.sort();
+        env
+    });
+    // rows are only rebuilt when one is added or removed, so typing keeps focus
+    let env_len = Memo::new(move |_| env.with(|e| e.len()));
     let (error, set_error) = signal(None);
     let (submitting, set_submitting) = signal(false);
+    let (availability, set_availability) = signal::<Option<Availability>>(None);
+
+    // ask the server as the user types, ignoring answers for stale values
+    Effect::new(move |_| {
+        let (Ok(id), Ok(p)) = (chain_id.get().parse::<u64>(), port.get().parse::<u16>()) else {
+            set_availability.set(None);
+            return;
+        };
+        spawn_local(async move {
+            let current = (chain_id.get_untracked(), port.get_untracked());
+            if let Ok(a) = Api::instance().availability(id, p).await {
+                if current == (chain_id.get_untracked(), port.get_untracked()) {
+                    set_availability.set(Some(a));
+                }
+            }
+        });
+    });
 
     // clones for handlers to avoid moving the originals
     let on_close_submit = on_close.clone();
     let on_created_submit = on_created.clone();
     let on_close_cancel = on_close.clone();
 
+    let parse_tags = move || {
+        // keeps the first of each repeated tag, wherever it repeats
+        let mut seen = HashSet::new();
+        tags.get()
+            .split(',')
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty() && seen.insert(t.clone()))
+            .collect::<Vec<String>>()
+    };
+
+    let build_config = move || ChainConfig {
+        name: name.get(),
+        id: chain_id.get().parse().unwrap_or(31337),
+        port: match auto_port.get() {
+            true => AUTO_PORT,
+            false => port.get().parse().unwrap_or(8545),
+        },
+        block_time: block_time.get().parse().unwrap_or(0),
+        status: ChainStatus::Stopped,
+        fork_url: fork_url.get(),
+        fork_block_number: fork_block.get().trim().parse().ok(),
+        pin_fork_block: pin_fork_block.get(),
+        color: Some(color.get().trim().to_string()).filter(|c| !c.is_empty()),
+        tags: parse_tags(),
+        notes: Some(notes.get().trim().to_string()).filter(|n| !n.is_empty()),
+        symbol: Some(symbol.get().trim().to_string()).filter(|s| !s.is_empty()),
+        decimals: decimals.get().trim().parse().ok(),
+        tracing: tracing.get(),
+        record_transactions: record_transactions.get(),
+        auto_start: auto_start.get(),
+        runtime: docker.get().then_some(Runtime::Docker),
+        env: env
+            .get()
+            .into_iter()
+            .map(|(k, v)| (k.trim().to_string(), v))
+            .filter(|(k, _)| !k.is_empty())
+            .collect(),
+        extra_args: split_args(&extra_args.get()).unwrap_or_default(),
+        host: Some(host.get().trim().to_string()).filter(|h| !h.is_empty()),
+        genesis_timestamp: genesis_timestamp.get().trim().parse().ok(),
+        gas_price: gas_price.get().trim().parse().ok(),
+        block_gas_limit: block_gas_limit.get().trim().parse().ok(),
+        heartbeat_secs: heartbeat.get().trim().parse().ok(),
+        premine_blocks: premine.get().trim().parse().ok(),
+        log_retention: log_retention.get().trim().parse().ok(),
+        ..Default::default()
+    };
+
     let validate = move || {
         let n = name.get();
-        if n.trim().is_empty() {
-            return Err("Name is required".to_string());
-        }
-        if !n
-            .chars()
-            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
-        {
-            return Err("Name must be alphanumeric (dash/underscore allowed)".to_string());
-        }
         if existing_chains
             .iter()
             .any(|e| e.name.eq_ignore_ascii_case(&n))
@@ -237,17 +815,40 @@ fn NewChainModal(
             return Err("Chain ID must be unique".to_string());
         }
 
-        let _port: u16 = port.get().parse().map_err(|_| "Invalid Port".to_string())?;
-        if existing_chains.iter().any(|e| e.port == _port) {
-            return Err("Port must be unique".to_string());
+        if !auto_port.get() {
+            let _port: u16 = port.get().parse().map_err(|_| "Invalid Port".to_string())?;
+            if existing_chains.iter().any(|e| e.port == _port) {
+                return Err("Port must be unique".to_string());
+            }
+        }
+
+        block_time
+            .get()
+            .parse::<u64>()
+            .map_err(|_| "Invalid Block Time".to_string())?;
+
+        split_args(&extra_args.get()).map_err(|e| format!("Invalid extra args: {}", e))?;
+
+        for (label, value) in [
+            ("Fork Block", fork_block.get()),
+            ("Genesis Timestamp", genesis_timestamp.get()),
+            ("Gas Price", gas_price.get()),
+            ("Block Gas Limit", block_gas_limit.get()),
+            ("Heartbeat", heartbeat.get()),
+            ("Pre-mined Blocks", premine.get()),
+            ("Log Retention", log_retention.get()),
+        ] {
+            if !value.trim().is_empty() && value.trim().parse::<u64>().is_err() {
+                return Err(format!("Invalid {}", label));
+            }
         }
 
-        // block_time must be grater greater than 0
-        let _bt: u64 = block_time.get().parse().unwrap_or(1);
-        if _bt == 0 {
-            return Err("Block time must be greater than 0".to_string());
+        let d = decimals.get();
+        if !d.trim().is_empty() && d.trim().parse::<u8>().is_err() {
+            return Err("Invalid Decimals".to_string());
         }
-        Ok(())
+
+        build_config().validate(allow_privileged_ports)
     };
 
     let submit = move |_| {
@@ -257,14 +858,7 @@ fn NewChainModal(
             return;
         }
         set_submitting.set(true);
-        let cfg = ChainConfig {
-            name: name.get(),
-            id: chain_id.get().parse().unwrap_or(31337),
-            port: port.get().parse().unwrap_or(8545),
-            block_time: block_time.get().parse().unwrap_or(0),
-            status: ChainStatus::Stopped,
-            fork_url: fork_url.get(),
-        };
+        let cfg = build_config();
         let on_created_cb = on_created_submit.clone();
         let on_close_cb = on_close_submit.clone();
         spawn_local(async move {
@@ -273,7 +867,7 @@ fn NewChainModal(
                     on_created_cb.as_ref()(new_cfg.id);
                     on_close_cb.as_ref()();
                 }
-                Err(e) => set_error.set(Some(e)),
+                Err(e) => set_error.set(Some(e.to_string())),
             }
             set_submitting.set(false);
         });
@@ -281,7 +875,7 @@ fn NewChainModal(
 
     view! {
         <div style="position:fixed; inset:0; background:rgba(0,0,0,0.4); display:flex; align-items:center; justify-content:center;">
-            <div style="background:white; padding:16px; width:420px; border-radius:8px; box-shadow:0 10px 25px rgba(0,0,0,0.2);">
+            <div style="background:white; padding:16px; width:420px; max-height:90vh; overflow:auto; border-radius:8px; box-shadow:0 10px 25px rgba(0,0,0,0.2);">
                 <div style="font-weight:600; font-size:16px; margin-bottom:12px;">New Chain</div>
                 {move || {
                     error
@@ -311,18 +905,51 @@ fn NewChainModal(
                             inputmode="numeric"
                             style="width:100%; padding:6px; border:1px solid #e5e7eb; border-radius:6px;"
                         />
+                        {move || {
+                            (availability.get().and_then(|a| a.id_available) == Some(false))
+                                .then(|| {
+                                    view! {
+                                        <div style="color:#b91c1c; font-size:12px; margin-top:2px;">
+                                            {"Chain ID is already used"}
+                                        </div>
+                                    }
+                                })
+                        }}
                     </label>
-                    <label>
-                        Port
+                    <div>
+                        <div style="display:flex; justify-content:space-between; align-items:center;">
+                            Port
+                            <label style="display:flex; gap:4px; align-items:center; font-size:12px; color:#6b7280;">
+                                <input
+                                    type="checkbox"
+                                    prop:checked=move || auto_port.get()
+                                    on:change=move |ev| set_auto_port.set(event_target_checked(&ev))
+                                />
+                                auto
+                            </label>
+                        </div>
                         <input
-                            prop:value=move || port.get()
+                            prop:value=move || if auto_port.get() { String::new() } else { port.get() }
                             on:input=move |ev| set_port.set(event_target_value(&ev))
+                            disabled=move || auto_port.get()
+                            placeholder="picked by the server"
                             inputmode="numeric"
                             style="width:100%; padding:6px; border:1px solid #e5e7eb; border-radius:6px;"
                         />
-                    </label>
+                        {move || {
+                            (!auto_port.get()
+                                && availability.get().and_then(|a| a.port_available) == Some(false))
+                                .then(|| {
+                                    view! {
+                                        <div style="color:#b91c1c; font-size:12px; margin-top:2px;">
+                                            {"Port is in use"}
+                                        </div>
+                                    }
+                                })
+                        }}
+                    </div>
                     <label>
-                        Block Time (s)
+                        Block Time (s, 0 mines per transaction)
                         <input
                             prop:value=move || block_time.get()
                             on:input=move |ev| set_block_time.set(event_target_value(&ev))
@@ -331,13 +958,265 @@ fn NewChainModal(
                         />
                     </label>
                     <label>
-                        Fork URL
+                        Color
+                        <div style="display:flex; gap:8px; align-items:center;">
+                            <input
+                                prop:value=move || color.get()
+                                on:input=move |ev| set_color.set(event_target_value(&ev))
+                                placeholder="#3b82f6"
+                                style="flex:1; padding:6px; border:1px solid #e5e7eb; border-radius:6px;"
+                            />
+                            <span style=move || {
+                                format!(
+                                    "width:24px; height:24px; border:1px solid #e5e7eb; border-radius:6px; background:{};",
+                                    color.get(),
+                                )
+                            }></span>
+                        </div>
+                    </label>
+                    <label>
+                        Tags (comma separated)
                         <input
-                            prop:value=move || fork_url.get()
-                            on:input=move |ev| set_fork_url.set(Some(event_target_value(&ev)))
+                            prop:value=move || tags.get()
+                            on:input=move |ev| set_tags.set(event_target_value(&ev))
+                            placeholder="mainnet-fork, oracle"
                             style="width:100%; padding:6px; border:1px solid #e5e7eb; border-radius:6px;"
                         />
                     </label>
+                    <label>
+                        Notes
+                        <textarea
+                            prop:value=move || notes.get()
+                            on:input=move |ev| set_notes.set(event_target_value(&ev))
+                            placeholder="What this chain is for"
+                            rows="3"
+                            style="width:100%; padding:6px; border:1px solid #e5e7eb; border-radius:6px; font-family:inherit;"
+                        ></textarea>
+                    </label>
+                    <label style="display:flex; gap:8px; align-items:center;">
+                        <input
+                            type="checkbox"
+                            prop:checked=move || auto_start.get()
+                            on:change=move |ev| set_auto_start.set(event_target_checked(&ev))
+                        />
+                        Start automatically when the server boots
+                    </label>
+                    <button
+                        on:click=move |_| {
+                            let expanded = !show_advanced.get();
+                            set_show_advanced.set(expanded);
+                            let _ = LocalStorage::set(ADVANCED_KEY, expanded);
+                        }
+                        style="align-self:flex-start; background:none; border:none; padding:0; color:#2563eb; cursor:pointer;"
+                    >
+                        {move || if show_advanced.get() { "▾ Advanced" } else { "▸ Advanced" }}
+                    </button>
+                    <div style:display=move || if show_advanced.get() { "flex" } else { "none" } style="flex-direction:column; gap:8px;">
+                        <label>
+                            Fork URL
+                            <input
+                                prop:value=move || fork_url.get()
+                                on:input=move |ev| set_fork_url.set(Some(event_target_value(&ev)))
+                                style="width:100%; padding:6px; border:1px solid #e5e7eb; border-radius:6px;"
+                            />
+                        </label>
+                        <ForkBookmarks fork_url=fork_url set_fork_url=set_fork_url />
+                        <div style="display:flex; gap:8px; align-items:flex-end;">
+                            <label style="flex:1;">
+                                Fork Block
+                                <input
+                                    prop:value=move || fork_block.get()
+                                    on:input=move |ev| set_fork_block.set(event_target_value(&ev))
+                                    placeholder="latest"
+                                    inputmode="numeric"
+                                    style="width:100%; padding:6px; border:1px solid #e5e7eb; border-radius:6px;"
+                                />
+                            </label>
+                            <label style="flex:1; display:flex; gap:8px; align-items:center; padding-bottom:6px;">
+                                <input
+                                    type="checkbox"
+                                    prop:checked=move || pin_fork_block.get()
+                                    on:change=move |ev| set_pin_fork_block.set(event_target_checked(&ev))
+                                    disabled=move || !fork_block.get().trim().is_empty()
+                                />
+                                Pin latest on first start
+                            </label>
+                        </div>
+                        <div style="display:flex; gap:8px;">
+                            <label style="flex:1;">
+                                Currency Symbol
+                                <input
+                                    prop:value=move || symbol.get()
+                                    on:input=move |ev| set_symbol.set(event_target_value(&ev))
+                                    placeholder=DEFAULT_SYMBOL
+                                    style="width:100%; padding:6px; border:1px solid #e5e7eb; border-radius:6px;"
+                                />
+                            </label>
+                            <label style="flex:1;">
+                                Decimals
+                                <input
+                                    prop:value=move || decimals.get()
+                                    on:input=move |ev| set_decimals.set(event_target_value(&ev))
+                                    placeholder=DEFAULT_DECIMALS.to_string()
+                                    inputmode="numeric"
+                                    style="width:100%; padding:6px; border:1px solid #e5e7eb; border-radius:6px;"
+                                />
+                            </label>
+                        </div>
+                        <label>
+                            Host
+                            <input
+                                prop:value=move || host.get()
+                                on:input=move |ev| set_host.set(event_target_value(&ev))
+                                placeholder=DEFAULT_HOST
+                                style="width:100%; padding:6px; border:1px solid #e5e7eb; border-radius:6px;"
+                            />
+                        </label>
+                        <div style="display:grid; grid-template-columns:1fr 1fr; gap:8px;">
+                            <label>
+                                Genesis Timestamp (unix s)
+                                <input
+                                    prop:value=move || genesis_timestamp.get()
+                                    on:input=move |ev| set_genesis_timestamp.set(event_target_value(&ev))
+                                    placeholder="now"
+                                    inputmode="numeric"
+                                    style="width:100%; padding:6px; border:1px solid #e5e7eb; border-radius:6px;"
+                                />
+                            </label>
+                            <label>
+                                Gas Price (wei)
+                                <input
+                                    prop:value=move || gas_price.get()
+                                    on:input=move |ev| set_gas_price.set(event_target_value(&ev))
+                                    inputmode="numeric"
+                                    style="width:100%; padding:6px; border:1px solid #e5e7eb; border-radius:6px;"
+                                />
+                            </label>
+                            <label>
+                                Block Gas Limit
+                                <input
+                                    prop:value=move || block_gas_limit.get()
+                                    on:input=move |ev| set_block_gas_limit.set(event_target_value(&ev))
+                                    placeholder="anvil default"
+                                    inputmode="numeric"
+                                    style="width:100%; padding:6px; border:1px solid #e5e7eb; border-radius:6px;"
+                                />
+                            </label>
+                            <label>
+                                Pre-mined Blocks
+                                <input
+                                    prop:value=move || premine.get()
+                                    on:input=move |ev| set_premine.set(event_target_value(&ev))
+                                    placeholder="0"
+                                    title="Blocks mined right after start"
+                                    inputmode="numeric"
+                                    style="width:100%; padding:6px; border:1px solid #e5e7eb; border-radius:6px;"
+                                />
+                            </label>
+                            <label>
+                                Heartbeat (s)
+                                <input
+                                    prop:value=move || heartbeat.get()
+                                    on:input=move |ev| set_heartbeat.set(event_target_value(&ev))
+                                    placeholder="off"
+                                    title="With a block time of 0, mine an empty block this often"
+                                    inputmode="numeric"
+                                    style="width:100%; padding:6px; border:1px solid #e5e7eb; border-radius:6px;"
+                                />
+                            </label>
+                            <label>
+                                Log Retention (lines)
+                                <input
+                                    prop:value=move || log_retention.get()
+                                    on:input=move |ev| set_log_retention.set(event_target_value(&ev))
+                                    placeholder="server default"
+                                    title="Log lines kept in memory for this chain"
+                                    inputmode="numeric"
+                                    style="width:100%; padding:6px; border:1px solid #e5e7eb; border-radius:6px;"
+                                />
+                            </label>
+                        </div>
+                        <label style="display:flex; gap:8px; align-items:center;">
+                            <input
+                                type="checkbox"
+                                prop:checked=move || tracing.get()
+                                on:change=move |ev| set_tracing.set(event_target_checked(&ev))
+                            />
+                            Steps tracing (opcode-level transaction traces)
+                        </label>
+                        <label style="display:flex; gap:8px; align-items:center;">
+                            <input
+                                type="checkbox"
+                                prop:checked=move || docker.get()
+                                on:change=move |ev| set_docker.set(event_target_checked(&ev))
+                            />
+                            Run in Docker (Foundry image, no local anvil needed)
+                        </label>
+                        <label style="display:flex; gap:8px; align-items:center;">
+                            <input
+                                type="checkbox"
+                                prop:checked=move || record_transactions.get()
+                                on:change=move |ev| set_record_transactions.set(event_target_checked(&ev))
+                            />
+                            Record transactions (for replay on another chain)
+                        </label>
+                        <div>
+                            Environment Variables
+                            <div style="display:flex; flex-direction:column; gap:4px; margin-top:4px;">
+                                {move || {
+                                    (0..env_len.get())
+                                        .map(|i| {
+                                            view! {
+                                                <div style="display:flex; gap:4px;">
+                                                    <input
+                                                        prop:value=move || env.get().get(i).map(|e| e.0.clone())
+                                                        on:input=move |ev| {
+                                                            set_env.update(|e| e[i].0 = event_target_value(&ev))
+                                                        }
+                                                        placeholder="RUST_LOG"
+                                                        style="flex:1; padding:6px; border:1px solid #e5e7eb; border-radius:6px; font-family:monospace;"
+                                                    />
+                                                    <input
+                                                        prop:value=move || env.get().get(i).map(|e| e.1.clone())
+                                                        on:input=move |ev| {
+                                                            set_env.update(|e| e[i].1 = event_target_value(&ev))
+                                                        }
+                                                        placeholder="debug"
+                                                        style="flex:1; padding:6px; border:1px solid #e5e7eb; border-radius:6px; font-family:monospace;"
+                                                    />
+                                                    <button
+                                                        on:click=move |_| {
+                                                            set_env.update(|e| {
+                                                                e.remove(i);
+                                                            })
+                                                        }
+                                                        style="background:white; border:1px solid #d1d5db; padding:4px 8px; border-radius:6px; cursor:pointer;"
+                                                    >
+                                                        {"×"}
+                                                    </button>
+                                                </div>
+                                            }
+                                        })
+                                        .collect_view()
+                                }}
+                                <button
+                                    on:click=move |_| set_env.update(|e| e.push(Default::default()))
+                                    style="align-self:flex-start; background:white; border:1px solid #d1d5db; padding:4px 8px; border-radius:6px; cursor:pointer;"
+                                >
+                                    {"+ Add variable"}
+                                </button>
+                            </div>
+                        </div>
+                        <label>
+                            Extra anvil arguments
+                            <input
+                                prop:value=move || extra_args.get()
+                                on:input=move |ev| set_extra_args.set(event_target_value(&ev))
+                                placeholder="--accounts 20 --balance 1000000"
+                                style="width:100%; padding:6px; border:1px solid #e5e7eb; border-radius:6px; font-family:monospace;"
+                            />
+                        </label>
+                    </div>
                 </div>
                 <div style="display:flex; gap:8px; justify-content:flex-end; margin-top:12px;">
                     {
@@ -364,10 +1243,156 @@ fn NewChainModal(
     }
 }
 
+/// Badge text for a chain status. Glyphs are escaped so they survive editors
+/// that don't save as UTF-8.
+fn status_label(status: ChainStatus) -> &'static str {
+    match status {
+        ChainStatus::Stopped => "\u{1F534} Stopped",
+        ChainStatus::Running => "\u{1F7E2} Running",
+        ChainStatus::Starting => "\u{1F7E1} Starting",
+        ChainStatus::Error => "\u{1F7E0} Error",
+    }
+}
+
 #[component]
-fn ChainColumn(chain: ChainConfig, on_action: Rc<dyn Fn(&'static str)>) -> impl IntoView {
+fn ChainColumn(
+    chain: ChainConfig,
+    status: Signal<ChainStatus>,
+    /// Chain id anvil reports while running
+    actual_chain_id: Signal<Option<u64>>,
+    /// Upstream head a pinned fork was started at
+    resolved_fork_block: Signal<Option<u64>>,
+    pinned: Signal<bool>,
+    /// Action sent to the server and not answered yet
+    pending: Signal<Option<&'static str>>,
+    on_action: Rc<dyn Fn(&'static str)>,
+    read_only: ReadSignal<bool>,
+    /// Changes when "Clear All Logs" is clicked
+    clear_logs: ReadSignal<u64>,
+) -> impl IntoView {
     let (show_info, set_show_info) = signal(false);
+    let (live, set_live) = signal::<Option<ChainStatusEvent>>(None);
+    // hash or error of the last "Test Tx" click
+    let (test_tx, set_test_tx) = signal::<Option<Result<String, String>>>(None);
+    let id = chain.id;
+    let (name, set_name) = signal(chain.name.clone());
+    // Some while the header name is being edited
+    let (name_draft, set_name_draft) = signal::<Option<String>>(None);
+    let (rename_error, set_rename_error) = signal::<Option<String>>(None);
+    let submit_rename = move || {
+        let Some(draft) = name_draft.get_untracked() else {
+            return;
+        };
+        let draft = draft.trim().to_string();
+        if draft == name.get_untracked() || draft.is_empty() {
+            set_name_draft.set(None);
+            return;
+        }
+        spawn_local(async move {
+            match Api::instance().rename_chain(id, &draft).await {
+                Ok(cfg) => {
+                    set_name.set(cfg.name);
+                    set_name_draft.set(None);
+                    set_rename_error.set(None);
+                }
+                Err(e) => set_rename_error.set(Some(e.to_string())),
+            }
+        });
+    };
+    let (notes, set_notes) = signal(chain.notes.clone());
+    // Some while the notes are being edited in the Info panel
+    let (notes_draft, set_notes_draft) = signal::<Option<String>>(None);
+    let (notes_error, set_notes_error) = signal::<Option<String>>(None);
+    let save_notes = move || {
+        let Some(draft) = notes_draft.get_untracked() else {
+            return;
+        };
+        spawn_local(async move {
+            match Api::instance().set_notes(id, draft.trim()).await {
+                Ok(cfg) => {
+                    set_notes.set(cfg.notes);
+                    set_notes_draft.set(None);
+                    set_notes_error.set(None);
+                }
+                Err(e) => set_notes_error.set(Some(e.to_string())),
+            }
+        });
+    };
+    let (block_time, set_block_time) = signal(chain.block_time);
+    let (block_time_draft, set_block_time_draft) = signal(chain.block_time.to_string());
+    let (block_time_error, set_block_time_error) = signal::<Option<String>>(None);
+    let submit_block_time = move || {
+        let Ok(secs) = block_time_draft.get_untracked().trim().parse::<u64>() else {
+            set_block_time_error.set(Some("Block time must be a whole number of seconds".into()));
+            return;
+        };
+        if secs == block_time.get_untracked() {
+            set_block_time_error.set(None);
+            return;
+        }
+        spawn_local(async move {
+            match Api::instance().set_block_time(id, secs).await {
+                Ok(cfg) => {
+                    // a new block time also resumes paused mining
+                    set_live.update(|e| {
+                        if let Some(e) = e {
+                            e.mining_paused = false;
+                        }
+                    });
+                    set_block_time.set(cfg.block_time);
+                    set_block_time_draft.set(cfg.block_time.to_string());
+                    set_block_time_error.set(None);
+                }
+                Err(e) => set_block_time_error.set(Some(e.to_string())),
+            }
+        });
+    };
+    let (gas_limit, set_gas_limit) = signal(chain.block_gas_limit);
+    let (gas_limit_draft, set_gas_limit_draft) = signal(
+        chain
+            .block_gas_limit
+            .map(|l| l.to_string())
+            .unwrap_or_default(),
+    );
+    let (gas_limit_error, set_gas_limit_error) = signal::<Option<String>>(None);
+    let submit_gas_limit = move || {
+        let Ok(limit) = gas_limit_draft.get_untracked().trim().parse::<u64>() else {
+            set_gas_limit_error.set(Some("Gas limit must be a whole number".into()));
+            return;
+        };
+        if limit < MIN_BLOCK_GAS_LIMIT {
+            set_gas_limit_error.set(Some(format!(
+                "Gas limit must be at least {}",
+                MIN_BLOCK_GAS_LIMIT
+            )));
+            return;
+        }
+        if Some(limit) == gas_limit.get_untracked() {
+            set_gas_limit_error.set(None);
+            return;
+        }
+        spawn_local(async move {
+            match Api::instance().set_block_gas_limit(id, limit).await {
+                Ok(cfg) => {
+                    set_gas_limit.set(cfg.block_gas_limit);
+                    set_gas_limit_error.set(None);
+                }
+                Err(e) => set_gas_limit_error.set(Some(e.to_string())),
+            }
+        });
+    };
+    // the status stream is fresher than the list, fall back to the list
+    // until it connects
+    let status = Signal::derive(move || live.get().map(|e| e.status).unwrap_or(status.get()));
+    let (usage, set_usage) = signal::<Option<ResourceUsage>>(None);
     let (logs, set_logs) = signal(Vec::<String>::new());
+    Effect::new(move |prev: Option<u64>| {
+        let count = clear_logs.get();
+        if prev.is_some() {
+            set_logs.set(vec![]);
+        }
+        count
+    });
     let (blocks, set_blocks) = signal(Vec::<Block>::new());
 
     #[derive(Clone)]
@@ -377,32 +1402,67 @@ fn ChainColumn(chain: ChainConfig, on_action: Rc<dyn Fn(&'static str)>) -> impl
     }
     let (active_tab, set_active_tab) = signal(Tabs::Logs);
 
-    let id = chain.id;
+    // backfill blocks produced before the column subscribed, e.g. after a
+    // page reload
+    Effect::new(move |_| {
+        if status.get() != ChainStatus::Running {
+            return;
+        }
+        spawn_local(async move {
+            let api = Api::instance();
+            let Ok(head) = api.latest_block_number(id).await else {
+                return;
+            };
+            let from = head.saturating_sub(RECENT_BLOCKS - 1);
+            match api.get_blocks_range(id, from, head).await {
+                Ok(recent) => set_blocks.update(|blocks| merge_blocks(blocks, recent)),
+                Err(e) => console_error(format!("Error loading recent blocks: {}", e).as_ref()),
+            }
+        });
+    });
+
+    // refresh memory usage whenever the Info panel is opened on a running chain
+    Effect::new(move |_| {
+        if show_info.get() && status.get() == ChainStatus::Running {
+            spawn_local(async move {
+                set_usage.set(Api::instance().chain_usage(id).await.ok());
+            });
+        } else {
+            set_usage.set(None);
+        }
+    });
 
     Effect::new({
         move |_| {
-            spawn_local(async move {
-                match Api::instance().log_stream(id) {
-                    Ok(mut es) => {
-                        let stdout = es.subscribe("message").unwrap();
-                        pin_mut!(stdout);
+            spawn_local(follow_logs(id, Some(LOG_TAIL), set_logs));
+        }
+    });
 
-                        while let Some(Ok((_event_type, msg))) = stdout.next().await {
-                            if let Some(msg) = msg.data().as_string() {
-                                set_logs.update(|v| v.push(msg));
-                            } else {
-                                console_error(
-                                    format!("Error reading SSE message: {:?}", msg).as_ref(),
-                                );
-                            }
+    Effect::new(move |_| {
+        spawn_local(async move {
+            match Api::instance().status_stream(id) {
+                Ok(mut es) => {
+                    let events = es.subscribe("message").unwrap();
+                    pin_mut!(events);
+
+                    while let Some(Ok((_event_type, msg))) = events.next().await {
+                        match msg
+                            .data()
+                            .as_string()
+                            .map(|data| serde_json::from_str::<ChainStatusEvent>(&data))
+                        {
+                            Some(Ok(event)) => set_live.set(Some(event)),
+                            _ => console_error(
+                                format!("Error reading status event: {:?}", msg).as_ref(),
+                            ),
                         }
                     }
-                    Err(e) => {
-                        console_error(format!("Error reading SSE message: {:?}", e).as_ref());
-                    }
                 }
-            });
-        }
+                Err(e) => {
+                    console_error(format!("Error reading SSE message: {:?}", e).as_ref());
+                }
+            }
+        });
     });
 
     Effect::new({
@@ -416,7 +1476,7 @@ fn ChainColumn(chain: ChainConfig, on_action: Rc<dyn Fn(&'static str)>) -> impl
                         while let Some(Ok((_event_type, msg))) = events.next().await {
                             if let Some(msg) = msg.data().as_string() {
                                 if let Ok(block) = Block::from_json(&msg) {
-                                    set_blocks.update(|v| v.push(block));
+                                    set_blocks.update(|v| merge_blocks(v, [block]));
                                 } else {
                                     console_error(
                                         format!("Error parsing block: {:?}", msg).as_ref(),
@@ -437,24 +1497,207 @@ fn ChainColumn(chain: ChainConfig, on_action: Rc<dyn Fn(&'static str)>) -> impl
         }
     });
 
-    let status_text = match chain.status {
-        ChainStatus::Stopped => "🔴 Stopped",
-        ChainStatus::Running => "🟢 Running",
-        ChainStatus::Starting => "🟡 Starting",
-        ChainStatus::Error => "🟠 Error",
+    // glyphs are escaped so they survive editors that don't save as UTF-8
+    let status_text = move || match (pending.get(), status.get()) {
+        (Some("start" | "restart" | "restart?preserve=true"), _) => "\u{1F7E1} Starting...",
+        (Some("stop"), _) => "\u{1F7E1} Stopping...",
+        (Some("fork-local"), _) => "\u{1F7E1} Forking...",
+        (Some(_), _) => "\u{1F7E1} Working...",
+        (None, status) => status_label(status),
+    };
+
+    // the last log line doubles as start progress, e.g. while a fork syncs
+    let starting = move || {
+        matches!(
+            pending.get(),
+            Some("start" | "restart" | "restart?preserve=true")
+        ) || status.get() == ChainStatus::Starting
+    };
+
+    // matches the badge glyphs above
+    let status_color = move || match (pending.get(), status.get()) {
+        (Some(_), _) | (None, ChainStatus::Starting) => "#f59e0b",
+        (None, ChainStatus::Stopped) => "#dc2626",
+        (None, ChainStatus::Running) => "#16a34a",
+        (None, ChainStatus::Error) => "#ea580c",
+    };
+
+    // blocks per second over the last RATE_WINDOW blocks and whether it is
+    // more than 50% off the configured block time
+    let block_rate = move || {
+        let block_time = block_time.get();
+        blocks.with(|blocks| {
+            // newest first
+            let window = &blocks[..blocks.len().min(RATE_WINDOW)];
+            let (newest, oldest) = (window.first()?, window.last()?);
+            let span = newest.time.checked_sub(oldest.time).filter(|s| *s > 0)?;
+            let rate = (window.len() - 1) as f64 / span as f64;
+            let drifting = block_time > 0 && {
+                let expected = 1.0 / block_time as f64;
+                (rate - expected).abs() > expected * 0.5
+            };
+            Some((rate, drifting))
+        })
     };
 
-    let can_start = matches!(chain.status, ChainStatus::Stopped);
-    let can_stop = matches!(chain.status, ChainStatus::Running);
-    let can_restart = matches!(chain.status, ChainStatus::Running | ChainStatus::Error);
+    let mut chain_info = format!(
+        "Chain ID: {}  •  RPC: {}  •  Currency: {} ({} decimals)",
+        chain.id,
+        chain.rpc_url(),
+        chain.symbol(),
+        chain.decimals(),
+    );
+    if let Some(ts) = chain.genesis_timestamp {
+        chain_info.push_str(&format!("  •  Genesis: {}", format_timestamp(ts)));
+    }
+    if let Some(gas_price) = chain.gas_price {
+        chain_info.push_str(&format!("  •  Gas Price: {} wei", gas_price));
+    }
+    if let Some(secs) = chain.heartbeat_secs {
+        chain_info.push_str(&format!("  •  Heartbeat: {}s", secs));
+    }
+    if chain.runtime() == Runtime::Docker {
+        chain_info.push_str("  •  Runtime: Docker");
+    }
+    let fork_block_number = chain.fork_block_number;
+    let fork_block_info = move || match (fork_block_number, resolved_fork_block.get()) {
+        (Some(number), _) => Some(format!("  •  Fork Block: {}", number)),
+        (None, Some(number)) => Some(format!("  •  Fork Block: {} (pinned)", number)),
+        (None, None) => None,
+    };
+
+    let busy = move || pending.get().is_some() || read_only.get();
+    // an HTTP-only anvil has no block subscription, the block pane stays empty
+    let streaming_unavailable = move || {
+        live.get()
+            .is_some_and(|e| e.status == ChainStatus::Running && !e.streaming_available)
+    };
+    let mining_paused = move || {
+        live.get()
+            .is_some_and(|e| e.status == ChainStatus::Running && e.mining_paused)
+    };
+    let toggle_mining = move || {
+        let paused = !mining_paused();
+        spawn_local(async move {
+            match Api::instance().set_mining_paused(id, paused).await {
+                // the status stream only repeats the flag every few seconds
+                Ok(()) => set_live.update(|e| {
+                    if let Some(e) = e {
+                        e.mining_paused = paused;
+                    }
+                }),
+                Err(e) => console_error(format!("Error setting mining: {}", e).as_ref()),
+            }
+        });
+    };
+    let mine_block = move || {
+        spawn_local(async move {
+            if let Err(e) = Api::instance().mine(id).await {
+                console_error(format!("Error mining block: {}", e).as_ref());
+            }
+        });
+    };
+    let can_start = move || matches!(status.get(), ChainStatus::Stopped);
+    let is_running = move || matches!(status.get(), ChainStatus::Running);
+    // an Error chain may still have anvil up, e.g. on a chain id mismatch or
+    // when interval mining stalls, so it can be stopped too
+    let can_stop = move || matches!(status.get(), ChainStatus::Running | ChainStatus::Error);
+    let can_restart = move || matches!(status.get(), ChainStatus::Running | ChainStatus::Error);
 
     view! {
-        <div style="min-width:380px; border:1px solid #e5e7eb; border-radius:8px; overflow:hidden; display:flex; flex-direction:column;">
-            <div style="display:flex; align-items:center; justify-content:space-between; padding:8px 10px; background:#f9fafb; border-bottom:1px solid #e5e7eb;">
-                <div style="font-weight:600;">{chain.name.clone()}</div>
+        <div style=move || {
+            format!(
+                "min-width:380px; border:1px solid #e5e7eb; border-left:4px solid {}; border-radius:8px; overflow:hidden; display:flex; flex-direction:column;",
+                status_color(),
+            )
+        }>
+            <div style=format!(
+                "display:flex; align-items:center; justify-content:space-between; padding:8px 10px; background:#f9fafb; border-bottom:1px solid #e5e7eb; border-top:4px solid {};",
+                chain.color.clone().unwrap_or("transparent".to_string()),
+            )>
+                <div style="display:flex; flex-direction:column; gap:4px;">
+                    <div style="display:flex; align-items:center; gap:4px;">
+                        {
+                            let on_action = on_action.clone();
+                            view! {
+                                <button
+                                    disabled=busy
+                                    on:click=move |_| {
+                                        on_action(if pinned.get_untracked() { "unpin" } else { "pin" })
+                                    }
+                                    title=move || if pinned.get() { "Unpin" } else { "Pin to the front" }
+                                    style=move || {
+                                        format!(
+                                            "background:none; border:none; padding:0; cursor:pointer; font-size:16px; color:{};",
+                                            if pinned.get() { "#f59e0b" } else { "#9ca3af" },
+                                        )
+                                    }
+                                >
+                                    {move || if pinned.get() { "\u{2605}" } else { "\u{2606}" }}
+                                </button>
+                            }
+                        }
+                        {move || match name_draft.get() {
+                            Some(draft) => {
+                                view! {
+                                    <input
+                                        prop:value=draft
+                                        on:input=move |ev| set_name_draft.set(Some(event_target_value(&ev)))
+                                        on:keydown=move |ev| match ev.key().as_str() {
+                                            "Enter" => submit_rename(),
+                                            "Escape" => {
+                                                set_name_draft.set(None);
+                                                set_rename_error.set(None);
+                                            }
+                                            _ => {}
+                                        }
+                                        on:blur=move |_| submit_rename()
+                                        autofocus
+                                        style="font-weight:600; padding:2px 4px; border:1px solid #d1d5db; border-radius:4px;"
+                                    />
+                                }
+                                    .into_any()
+                            }
+                            None => {
+                                view! {
+                                    <div
+                                        on:click=move |_| {
+                                            if !read_only.get_untracked() {
+                                                set_name_draft.set(Some(name.get_untracked()));
+                                            }
+                                        }
+                                        title="Click to rename"
+                                        style="font-weight:600; cursor:text;"
+                                    >
+                                        {move || name.get()}
+                                    </div>
+                                }
+                                    .into_any()
+                            }
+                        }}
+                    </div>
+                    {move || {
+                        rename_error
+                            .get()
+                            .map(|e| view! { <div style="font-size:11px; color:#b91c1c;">{e}</div> })
+                    }}
+                    <div style="display:flex; flex-wrap:wrap; gap:4px;">
+                        {chain
+                            .tags
+                            .iter()
+                            .map(|tag| {
+                                view! {
+                                    <span style="font-size:11px; padding:1px 6px; border:1px solid #e5e7eb; border-radius:9999px; background:white; color:#374151;">
+                                        {tag.clone()}
+                                    </span>
+                                }
+                            })
+                            .collect_view()}
+                    </div>
+                </div>
                 <div style="display:flex; align-items:center; gap:8px;">
                     {
-                        let action = set_active_tab.clone();
+                        let action = set_active_tab;
                         view! {
                             <button
                                 on:click=move |_| action.set(Tabs::Logs)
@@ -465,7 +1708,7 @@ fn ChainColumn(chain: ChainConfig, on_action: Rc<dyn Fn(&'static str)>) -> impl
                         }
                     }
                     {
-                        let action = set_active_tab.clone();
+                        let action = set_active_tab;
                         view! {
                             <button
                                 on:click=move |_| action.set(Tabs::Blocks)
@@ -475,16 +1718,106 @@ fn ChainColumn(chain: ChainConfig, on_action: Rc<dyn Fn(&'static str)>) -> impl
                             </button>
                         }
                     }
+                    <A href=format!("/{}/events", id)>
+                        <span style="display:inline-block; padding:6px 8px; border:1px solid #d1d5db; background:white; border-radius:6px; cursor:pointer; color:inherit; text-decoration:none;">
+                            {"Events"}
+                        </span>
+                    </A>
                 </div>
                 <div style="display:flex; align-items:center; gap:8px;">
                     <span style="font-size:12px; padding:2px 6px; border:1px solid #e5e7eb; border-radius:9999px; background:white;">
                         {status_text}
                     </span>
+                    {move || {
+                        mining_paused()
+                            .then(|| {
+                                view! {
+                                    <span
+                                        title="Blocks are only mined on request"
+                                        style="font-size:12px; padding:2px 6px; color:#92400e; background:#fef3c7; border-radius:9999px;"
+                                    >
+                                        {"Paused"}
+                                    </span>
+                                    <button
+                                        disabled=busy
+                                        on:click=move |_| mine_block()
+                                        style="padding:6px 10px; border:none; background:#2563eb; color:white; border-radius:6px; cursor:pointer; font-weight:600;"
+                                    >
+                                        {"Mine Block"}
+                                    </button>
+                                }
+                            })
+                    }}
+                    {move || {
+                        starting()
+                            .then(|| logs.with(|l| l.last().cloned()))
+                            .flatten()
+                            .map(|line| {
+                                let title = line.clone();
+                                view! {
+                                    <span
+                                        title=title
+                                        style="font-size:12px; color:#6b7280; max-width:220px; overflow:hidden; text-overflow:ellipsis; white-space:nowrap;"
+                                    >
+                                        {line}
+                                    </span>
+                                }
+                            })
+                    }}
+                    {move || {
+                        live.get()
+                            .filter(|e| e.status == ChainStatus::Running && !e.rpc_connected)
+                            .map(|_| {
+                                view! {
+                                    <span
+                                        title="The server lost its connection to anvil and is reconnecting"
+                                        style="font-size:12px; padding:2px 6px; color:#b91c1c; background:#fee2e2; border-radius:9999px;"
+                                    >
+                                        {"RPC disconnected"}
+                                    </span>
+                                }
+                            })
+                    }}
+                    {move || {
+                        live.get()
+                            .and_then(|e| e.latest_block)
+                            .map(|head| {
+                                view! {
+                                    <span
+                                        title=move || {
+                                            format!(
+                                                "Latest block, {} transactions since start",
+                                                live.get().map(|e| e.transactions).unwrap_or_default(),
+                                            )
+                                        }
+                                        style="font-size:12px; font-family:monospace; color:#374151;"
+                                    >
+                                        {format!("#{}", head)}
+                                    </span>
+                                }
+                            })
+                    }}
+                    {move || {
+                        block_rate()
+                            .map(|(rate, drifting)| {
+                                view! {
+                                    <span
+                                        title="Block production rate"
+                                        style=format!(
+                                            "font-size:12px; font-family:monospace; color:{};",
+                                            if drifting { "#dc2626" } else { "#6b7280" },
+                                        )
+                                    >
+                                        {format!("~{:.1} blk/s", rate)}
+                                    </span>
+                                }
+                            })
+                    }}
                     {
                         let on_action = on_action.clone();
                         view! {
                             <button
-                                disabled=move || !can_start
+                                disabled=move || !can_start() || busy()
                                 on:click=move |_| on_action("start")
                                 style="padding:6px 8px; border:1px solid #d1d5db; background:white; border-radius:6px; cursor:pointer;"
                             >
@@ -496,7 +1829,7 @@ fn ChainColumn(chain: ChainConfig, on_action: Rc<dyn Fn(&'static str)>) -> impl
                         let on_action = on_action.clone();
                         view! {
                             <button
-                                disabled=move || !can_stop
+                                disabled=move || !can_stop() || busy()
                                 on:click=move |_| on_action("stop")
                                 style="padding:6px 8px; border:1px solid #d1d5db; background:white; border-radius:6px; cursor:pointer;"
                             >
@@ -508,8 +1841,13 @@ fn ChainColumn(chain: ChainConfig, on_action: Rc<dyn Fn(&'static str)>) -> impl
                         let on_action = on_action.clone();
                         view! {
                             <button
-                                disabled=move || !can_restart
-                                on:click=move |_| on_action("restart")
+                                disabled=move || !can_restart() || busy()
+                                on:click=move |ev| {
+                                    on_action(
+                                        if ev.shift_key() { "restart?preserve=true" } else { "restart" },
+                                    )
+                                }
+                                title="Shift-click to keep the chain's state"
                                 style="padding:6px 8px; border:1px solid #d1d5db; background:white; border-radius:6px; cursor:pointer;"
                             >
                                 {"Restart"}
@@ -520,6 +1858,42 @@ fn ChainColumn(chain: ChainConfig, on_action: Rc<dyn Fn(&'static str)>) -> impl
                         let on_action = on_action.clone();
                         view! {
                             <button
+                                disabled=move || !is_running() || busy()
+                                on:click=move |_| on_action("fork-local")
+                                title="New chain starting from this chain's current state"
+                                style="padding:6px 8px; border:1px solid #d1d5db; background:white; border-radius:6px; cursor:pointer;"
+                            >
+                                {"Fork"}
+                            </button>
+                        }
+                    }
+                    <button
+                        disabled=move || !is_running() || busy()
+                        on:click=move |_| {
+                            set_test_tx.set(None);
+                            spawn_local(async move {
+                                let result = Api::instance().send_test_transaction(id).await;
+                                set_test_tx.set(Some(result.map_err(|e| e.to_string())));
+                            });
+                        }
+                        title="Send 1 wei between two prefunded accounts"
+                        style="padding:6px 8px; border:1px solid #d1d5db; background:white; border-radius:6px; cursor:pointer;"
+                    >
+                        {"Test Tx"}
+                    </button>
+                    <button
+                        disabled=move || !is_running() || busy()
+                        on:click=move |_| toggle_mining()
+                        title="Stop mining until resumed, blocks then only come from Mine Block"
+                        style="padding:6px 8px; border:1px solid #d1d5db; background:white; border-radius:6px; cursor:pointer;"
+                    >
+                        {move || if mining_paused() { "Resume Mining" } else { "Pause Mining" }}
+                    </button>
+                    {
+                        let on_action = on_action.clone();
+                        view! {
+                            <button
+                                disabled=busy
                                 on:click=move |_| on_action("delete")
                                 style="padding:6px 8px; border:1px solid #d1d5db; background:white; border-radius:6px; cursor:pointer;"
                             >
@@ -539,32 +1913,267 @@ fn ChainColumn(chain: ChainConfig, on_action: Rc<dyn Fn(&'static str)>) -> impl
                     >
                         {"Clear Log"}
                     </button>
+                    <button
+                        on:click=move |_| {
+                            spawn_local(async move {
+                                match Api::instance().download_logs(id).await {
+                                    Ok(text) => {
+                                        download_file(&format!("chain-{}.log", id), "text/plain", &text)
+                                    }
+                                    Err(e) => {
+                                        console_error(format!("Error downloading log: {}", e).as_ref())
+                                    }
+                                }
+                            });
+                        }
+                        style="padding:6px 8px; border:1px solid #d1d5db; background:white; border-radius:6px; cursor:pointer;"
+                    >
+                        {"Download Log"}
+                    </button>
+                    <a
+                        href=format!("/{}/logs", id)
+                        target="_blank"
+                        title="The whole log in a new tab"
+                        style="padding:6px 8px; border:1px solid #d1d5db; background:white; border-radius:6px; cursor:pointer; color:inherit; text-decoration:none;"
+                    >
+                        {"Open Logs"}
+                    </a>
                 </div>
             </div>
+            {move || {
+                test_tx
+                    .get()
+                    .map(|result| {
+                        let body = match result {
+                            Ok(hash) => {
+                                view! {
+                                    <span>{"Test transaction sent: "}</span>
+                                    <A href=format!("/{}/transactions/{}", id, hash)>
+                                        <span style="font-family:monospace; color:#1d4ed8;">
+                                            {format!("{}...", &hash[..hash.len().min(18)])}
+                                        </span>
+                                    </A>
+                                }
+                                    .into_any()
+                            }
+                            Err(e) => {
+                                view! { <span>{format!("Test transaction failed: {}", e)}</span> }
+                                    .into_any()
+                            }
+                        };
+                        view! {
+                            <div style="display:flex; align-items:center; gap:6px; padding:6px 10px; border-bottom:1px solid #e5e7eb; font-size:12px; background:#f0f9ff;">
+                                {body}
+                                <div style="flex:1;"></div>
+                                <button
+                                    on:click=move |_| set_test_tx.set(None)
+                                    style="background:none; border:none; cursor:pointer; color:#6b7280;"
+                                >
+                                    {"\u{2715}"}
+                                </button>
+                            </div>
+                        }
+                    })
+            }}
             {move || {
                 show_info
                     .get()
                     .then(|| {
                         view! {
                             <div style="padding:8px 10px; border-bottom:1px solid #e5e7eb; font-size:12px; color:#374151;">
-                                {format!(
-                                    "Chain ID: {}  •  Port: {}  •  Block Time: {}",
-                                    chain.id,
-                                    chain.port,
-                                    chain.block_time,
-                                )}
+                                {chain_info.clone()}
+                                {fork_block_info}
+                                <div style="display:flex; align-items:center; gap:6px; margin-top:4px;">
+                                    <span>{"Block Time:"}</span>
+                                    <input
+                                        type="number"
+                                        min="0"
+                                        prop:value=move || block_time_draft.get()
+                                        on:input=move |ev| set_block_time_draft.set(event_target_value(&ev))
+                                        on:keydown=move |ev| {
+                                            if ev.key() == "Enter" {
+                                                submit_block_time();
+                                            }
+                                        }
+                                        disabled=move || !is_running() || busy()
+                                        title="0 mines a block per transaction"
+                                        style="width:64px; padding:2px 4px; border:1px solid #d1d5db; border-radius:4px;"
+                                    />
+                                    <span>{"s"}</span>
+                                    <button
+                                        on:click=move |_| submit_block_time()
+                                        disabled=move || !is_running() || busy()
+                                        style="padding:2px 6px; border:1px solid #d1d5db; background:white; border-radius:4px; cursor:pointer;"
+                                    >
+                                        {"Set"}
+                                    </button>
+                                    {move || {
+                                        block_time_error
+                                            .get()
+                                            .map(|e| view! { <span style="color:#b91c1c;">{e}</span> })
+                                    }}
+                                </div>
+                                <div style="display:flex; align-items:center; gap:6px; margin-top:4px;">
+                                    <span>{"Block Gas Limit:"}</span>
+                                    <input
+                                        type="number"
+                                        min=MIN_BLOCK_GAS_LIMIT.to_string()
+                                        prop:value=move || gas_limit_draft.get()
+                                        on:input=move |ev| set_gas_limit_draft.set(event_target_value(&ev))
+                                        on:keydown=move |ev| {
+                                            if ev.key() == "Enter" {
+                                                submit_gas_limit();
+                                            }
+                                        }
+                                        disabled=move || !is_running() || busy()
+                                        placeholder="anvil default"
+                                        style="width:110px; padding:2px 4px; border:1px solid #d1d5db; border-radius:4px;"
+                                    />
+                                    <button
+                                        on:click=move |_| submit_gas_limit()
+                                        disabled=move || !is_running() || busy()
+                                        style="padding:2px 6px; border:1px solid #d1d5db; background:white; border-radius:4px; cursor:pointer;"
+                                    >
+                                        {"Set"}
+                                    </button>
+                                    {move || {
+                                        gas_limit_error
+                                            .get()
+                                            .map(|e| view! { <span style="color:#b91c1c;">{e}</span> })
+                                    }}
+                                </div>
+                                {move || match notes_draft.get() {
+                                    Some(draft) => {
+                                        view! {
+                                            <div style="display:flex; flex-direction:column; gap:4px; margin-top:4px;">
+                                                <textarea
+                                                    prop:value=draft
+                                                    on:input=move |ev| {
+                                                        set_notes_draft.set(Some(event_target_value(&ev)))
+                                                    }
+                                                    rows="3"
+                                                    style="padding:4px; border:1px solid #d1d5db; border-radius:4px; font-family:inherit; font-size:12px;"
+                                                ></textarea>
+                                                <div style="display:flex; gap:6px;">
+                                                    <button
+                                                        on:click=move |_| save_notes()
+                                                        style="padding:2px 6px; border:1px solid #d1d5db; background:white; border-radius:4px; cursor:pointer;"
+                                                    >
+                                                        {"Save"}
+                                                    </button>
+                                                    <button
+                                                        on:click=move |_| {
+                                                            set_notes_draft.set(None);
+                                                            set_notes_error.set(None);
+                                                        }
+                                                        style="padding:2px 6px; border:1px solid #d1d5db; background:white; border-radius:4px; cursor:pointer;"
+                                                    >
+                                                        {"Cancel"}
+                                                    </button>
+                                                    {move || {
+                                                        notes_error
+                                                            .get()
+                                                            .map(|e| view! { <span style="color:#b91c1c;">{e}</span> })
+                                                    }}
+                                                </div>
+                                            </div>
+                                        }
+                                            .into_any()
+                                    }
+                                    None => {
+                                        view! {
+                                            <div style="display:flex; align-items:flex-start; gap:6px; margin-top:4px;">
+                                                <span style="white-space:pre-wrap; color:#4b5563;">
+                                                    {move || notes.get().unwrap_or_else(|| "No notes".to_string())}
+                                                </span>
+                                                <button
+                                                    on:click=move |_| {
+                                                        set_notes_draft.set(Some(notes.get_untracked().unwrap_or_default()))
+                                                    }
+                                                    disabled=read_only
+                                                    style="background:none; border:none; padding:0; color:#2563eb; cursor:pointer;"
+                                                >
+                                                    {"Edit"}
+                                                </button>
+                                            </div>
+                                        }
+                                            .into_any()
+                                    }
+                                }}
+                                {move || {
+                                    actual_chain_id
+                                        .get()
+                                        .filter(|actual| *actual != id)
+                                        .map(|actual| {
+                                            view! {
+                                                <div style="color:#dc2626; margin-top:4px;">
+                                                    {format!(
+                                                        "Chain ID mismatch: configured {}, actual {}",
+                                                        id,
+                                                        actual,
+                                                    )}
+                                                </div>
+                                            }
+                                        })
+                                }}
+                                {move || {
+                                    usage
+                                        .get()
+                                        .map(|u| {
+                                            format!(
+                                                "  •  Memory: {:.1} MB  •  CPU: {:.1}s",
+                                                u.rss_bytes as f64 / (1024.0 * 1024.0),
+                                                u.cpu_time_ms as f64 / 1000.0,
+                                            )
+                                        })
+                                }}
                             </div>
                         }
                     })
             }}
             {move || {
                 match active_tab.get() {
-                    Tabs::Logs => view! { <LogsColumn logs=logs /> }.into_any(),
+                    Tabs::Logs => view! { <LogsColumn chain_id=id logs=logs /> }.into_any(),
+                    Tabs::Blocks if streaming_unavailable() => {
+                        view! {
+                            <div style="padding:12px; color:#6b7280; font-size:13px;">
+                                {"Block streaming unavailable, anvil only answers over HTTP"}
+                            </div>
+                        }
+                            .into_any()
+                    }
                     Tabs::Blocks => {
-                        view! { <BlocksColumn blocks=blocks chainid=chain.id /> }.into_any()
+                        view! {
+                            <GasSparkline blocks=blocks />
+                            <BlocksColumn
+                                blocks=blocks
+                                chainid=chain.id
+                                forked=chain.fork_url.is_some()
+                            />
+                        }
+                            .into_any()
                     }
                 }
             }}
         </div>
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_labels_carry_their_glyph() {
+        for (status, glyph) in [
+            (ChainStatus::Stopped, '\u{1F534}'),
+            (ChainStatus::Running, '\u{1F7E2}'),
+            (ChainStatus::Starting, '\u{1F7E1}'),
+            (ChainStatus::Error, '\u{1F7E0}'),
+        ] {
+            let label = status_label(status);
+            assert_eq!(label.chars().next(), Some(glyph), "{:?}", status);
+            assert_eq!(label.chars().nth(1), Some(' '));
+        }
+    }
+}
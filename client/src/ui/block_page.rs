@@ -1,10 +1,13 @@
 use crate::api::client::Api;
+use crate::ui::download::download_file;
+use crate::ui::hash_display::HashDisplay;
 use leptos::prelude::*;
 use leptos::task::spawn_local;
 use leptos_router::components::A;
 use leptos_router::hooks::use_navigate;
 use leptos_router::hooks::use_params_map;
 use shared::types::block_response::BlockResponse;
+use shared::types::chain_config::ChainConfig;
 use shared::types::transaction::Transaction;
 
 #[component]
@@ -17,6 +20,8 @@ pub fn BlockPage() -> impl IntoView {
     let navigate = use_navigate();
 
     let (block_data, set_block_data) = signal::<Option<BlockResponse>>(None);
+    // for the currency values are shown in, ETH until it has loaded
+    let (chain, set_chain) = signal(ChainConfig::default());
     let (loading, set_loading) = signal(false);
     let (error_msg, set_error_msg) = signal::<Option<String>>(None);
 
@@ -27,13 +32,21 @@ pub fn BlockPage() -> impl IntoView {
             set_block_data.set(None);
             let api = Api::instance();
             spawn_local(async move {
-                match api.get_block(cid, bnum).await {
+                if let Ok(config) = api.get_chain(cid).await {
+                    set_chain.set(config);
+                }
+                // the server caches block 0
+                let block = match bnum {
+                    0 => api.get_genesis(cid).await,
+                    _ => api.get_block(cid, bnum).await,
+                };
+                match block {
                     Ok(data) => {
                         set_block_data.set(Some(data));
                         set_error_msg.set(None);
                     }
                     Err(e) => {
-                        set_error_msg.set(Some(e));
+                        set_error_msg.set(Some(e.to_string()));
                         set_block_data.set(None);
                     }
                 }
@@ -42,15 +55,145 @@ pub fn BlockPage() -> impl IntoView {
         }
     });
 
+    let go_to_block = {
+        let navigate = navigate.clone();
+        move |number: u64| {
+            if let Some(cid) = chain_id() {
+                navigate(format!("/{}/{}", cid, number).as_str(), Default::default());
+            }
+        }
+    };
+    let go_to_prev = go_to_block.clone();
+    let go_to_next = go_to_block.clone();
+    let go_to_entered = go_to_block.clone();
+    let compare_with_prev = {
+        let navigate = navigate.clone();
+        move |_| {
+            if let (Some(cid), Some(number)) = (chain_id(), block_num().filter(|n| *n > 0)) {
+                navigate(
+                    format!("/{}/compare/{}/{}", cid, number - 1, number).as_str(),
+                    Default::default(),
+                );
+            }
+        }
+    };
+    let go_to_latest = move |_| {
+        let Some(cid) = chain_id() else {
+            return;
+        };
+        let go_to_block = go_to_block.clone();
+        spawn_local(async move {
+            match Api::instance().latest_block_number(cid).await {
+                Ok(number) => go_to_block(number),
+                Err(e) => set_error_msg.set(Some(e.to_string())),
+            }
+        });
+    };
+
+    let (jump_input, set_jump_input) = signal(String::new());
+    let (jump_error, set_jump_error) = signal::<Option<String>>(None);
+    // checked against the head so a typo doesn't land on an empty page
+    let jump = move || {
+        let Some(cid) = chain_id() else {
+            return;
+        };
+        let Ok(number) = jump_input.get_untracked().trim().parse::<u64>() else {
+            set_jump_error.set(Some("Enter a block number".to_string()));
+            return;
+        };
+        let go_to_block = go_to_entered.clone();
+        spawn_local(async move {
+            match Api::instance().latest_block_number(cid).await {
+                Ok(head) if number > head => set_jump_error.set(Some(format!(
+                    "Block {} is past the head ({})",
+                    number, head
+                ))),
+                Ok(_) => {
+                    set_jump_error.set(None);
+                    set_jump_input.set(String::new());
+                    go_to_block(number);
+                }
+                Err(e) => set_jump_error.set(Some(e.to_string())),
+            }
+        });
+    };
+    let jump_on_enter = jump.clone();
+
     view! {
         <div style="font-family: system-ui, -apple-system, Segoe UI, Roboto, Ubuntu, Cantarell, Noto Sans, Helvetica, Arial, Apple Color Emoji, Segoe UI Emoji; padding:16px;">
-            <div style="margin-bottom:16px;">
+            <div style="display:flex; gap:8px; margin-bottom:16px;">
                 <button
                     on:click=move |_| navigate("/", Default::default())
                     style="background:#2563eb; color:white; border:none; padding:8px 12px; border-radius:6px; cursor:pointer;"
                 >
                     {"← Back"}
                 </button>
+                <div style="flex:1;"></div>
+                {move || {
+                    jump_error
+                        .get()
+                        .map(|e| {
+                            view! {
+                                <span style="align-self:center; color:#b91c1c; font-size:13px;">
+                                    {e}
+                                </span>
+                            }
+                        })
+                }}
+                <input
+                    type="number"
+                    min="0"
+                    placeholder="Block #"
+                    prop:value=jump_input
+                    on:input=move |ev| set_jump_input.set(event_target_value(&ev))
+                    on:keydown=move |ev| {
+                        if ev.key() == "Enter" {
+                            jump_on_enter()
+                        }
+                    }
+                    style="width:110px; padding:8px; border:1px solid #d1d5db; border-radius:6px;"
+                />
+                <button
+                    on:click=move |_| jump()
+                    style="background:white; border:1px solid #d1d5db; padding:8px 12px; border-radius:6px; cursor:pointer;"
+                >
+                    {"Go"}
+                </button>
+                <button
+                    disabled=move || block_num().unwrap_or_default() == 0
+                    on:click=move |_| {
+                        if let Some(number) = block_num() {
+                            go_to_prev(number.saturating_sub(1))
+                        }
+                    }
+                    style="background:white; border:1px solid #d1d5db; padding:8px 12px; border-radius:6px; cursor:pointer;"
+                >
+                    {"← Prev Block"}
+                </button>
+                <button
+                    on:click=move |_| {
+                        if let Some(number) = block_num() {
+                            go_to_next(number + 1)
+                        }
+                    }
+                    style="background:white; border:1px solid #d1d5db; padding:8px 12px; border-radius:6px; cursor:pointer;"
+                >
+                    {"Next Block →"}
+                </button>
+                <button
+                    on:click=go_to_latest
+                    style="background:white; border:1px solid #d1d5db; padding:8px 12px; border-radius:6px; cursor:pointer;"
+                >
+                    {"Latest ⇥"}
+                </button>
+                <button
+                    disabled=move || block_num().unwrap_or_default() == 0
+                    on:click=compare_with_prev
+                    title="Compare this block with the one before it"
+                    style="background:white; border:1px solid #d1d5db; padding:8px 12px; border-radius:6px; cursor:pointer;"
+                >
+                    {"Compare ⇄ Prev"}
+                </button>
             </div>
             {move || {
                 match (chain_id(), block_num()) {
@@ -100,17 +243,13 @@ pub fn BlockPage() -> impl IntoView {
                                                 <div style="color:#6b7280; font-size:12px; font-weight:600; margin-bottom:4px;">
                                                     {"Hash"}
                                                 </div>
-                                                <div style="font-size:12px; font-family:monospace; word-break:break-all;">
-                                                    {block.hash.clone()}
-                                                </div>
+                                                <HashDisplay value=block.hash.clone() />
                                             </div>
                                             <div style="padding:8px; background:#f9fafb; border-radius:4px;">
                                                 <div style="color:#6b7280; font-size:12px; font-weight:600; margin-bottom:4px;">
                                                     {"Beneficiary"}
                                                 </div>
-                                                <div style="font-size:12px; font-family:monospace; word-break:break-all;">
-                                                    {block.beneficiary.clone()}
-                                                </div>
+                                                <HashDisplay value=block.beneficiary.clone() />
                                             </div>
                                             <div style="padding:8px; background:#f9fafb; border-radius:4px;">
                                                 <div style="color:#6b7280; font-size:12px; font-weight:600; margin-bottom:4px;">
@@ -140,9 +279,7 @@ pub fn BlockPage() -> impl IntoView {
                                                 <div style="color:#6b7280; font-size:12px; font-weight:600; margin-bottom:4px;">
                                                     {"Nonce"}
                                                 </div>
-                                                <div style="font-size:12px; font-family:monospace; word-break:break-all;">
-                                                    {block.nonce.clone()}
-                                                </div>
+                                                <HashDisplay value=block.nonce.clone() />
                                             </div>
                                             <div style="padding:8px; background:#f9fafb; border-radius:4px;">
                                                 <div style="color:#6b7280; font-size:12px; font-weight:600; margin-bottom:4px;">
@@ -155,9 +292,47 @@ pub fn BlockPage() -> impl IntoView {
                                         </div>
                                     </div>
                                     <div style="background:white; border:1px solid #e5e7eb; border-radius:8px; padding:16px;">
-                                        <h2 style="font-size:20px; font-weight:600; margin-bottom:16px;">
-                                            {"Transactions ("}{transactions.len()}{")"}
-                                        </h2>
+                                        <div style="display:flex; align-items:center; gap:8px; margin-bottom:16px;">
+                                            <h2 style="font-size:20px; font-weight:600; flex:1;">
+                                                {"Transactions ("}{transactions.len()}{")"}
+                                            </h2>
+                                            {(!transactions.is_empty())
+                                                .then(|| {
+                                                    let csv_txs = transactions.clone();
+                                                    let json_txs = transactions.clone();
+                                                    let name = format!(
+                                                        "block-{}-{}-transactions",
+                                                        cid,
+                                                        block.number,
+                                                    );
+                                                    let csv_name = format!("{}.csv", name);
+                                                    let json_name = format!("{}.json", name);
+                                                    view! {
+                                                        <button
+                                                            on:click=move |_| {
+                                                                download_file(
+                                                                    &csv_name,
+                                                                    "text/csv",
+                                                                    &transactions_csv(&csv_txs, &chain.get_untracked()),
+                                                                )
+                                                            }
+                                                            style="background:white; border:1px solid #d1d5db; padding:6px 10px; border-radius:6px; cursor:pointer;"
+                                                        >
+                                                            {"Download CSV"}
+                                                        </button>
+                                                        <button
+                                                            on:click=move |_| {
+                                                                if let Ok(json) = serde_json::to_string_pretty(&json_txs) {
+                                                                    download_file(&json_name, "application/json", &json)
+                                                                }
+                                                            }
+                                                            style="background:white; border:1px solid #d1d5db; padding:6px 10px; border-radius:6px; cursor:pointer;"
+                                                        >
+                                                            {"Download JSON"}
+                                                        </button>
+                                                    }
+                                                })}
+                                        </div>
                                         {if transactions.is_empty() {
                                             view! {
                                                 <div style="padding:16px; text-align:center; color:#6b7280;">
@@ -175,7 +350,10 @@ pub fn BlockPage() -> impl IntoView {
                                                         }
                                                         key=|(idx, _)| *idx
                                                         children=move |(idx, tx): (usize, Transaction)| {
-                                                            view! { <TransactionDetails tx=tx idx=idx chain_id=cid /> }
+                                                            let value = chain.get_untracked().format_value(&tx.value);
+                                                            view! {
+                                                                <TransactionDetails tx=tx idx=idx chain_id=cid value=value />
+                                                            }
                                                                 .into_any()
                                                         }
                                                     />
@@ -210,11 +388,38 @@ pub fn BlockPage() -> impl IntoView {
     }
 }
 
+/// Hash, addresses, value and gas of each transaction, one row per line.
+/// `value` is in wei and `amount` in the chain's currency, no field holds a
+/// comma or quote so nothing needs quoting.
+fn transactions_csv(transactions: &[Transaction], chain: &ChainConfig) -> String {
+    let mut csv = String::from("hash,block_number,index,from,to,value,gas,amount\n");
+    for tx in transactions {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{},{}\n",
+            tx.hash,
+            tx.block_number.map(|n| n.to_string()).unwrap_or_default(),
+            tx.index.map(|i| i.to_string()).unwrap_or_default(),
+            tx.from,
+            tx.to.as_deref().unwrap_or_default(),
+            tx.value,
+            tx.gas,
+            chain.format_value(&tx.value),
+        ));
+    }
+    csv
+}
+
 #[component]
-pub fn TransactionDetails(tx: Transaction, idx: usize, chain_id: u64) -> impl IntoView {
+pub fn TransactionDetails(
+    tx: Transaction,
+    idx: usize,
+    chain_id: u64,
+    /// `tx.value` in the chain's currency
+    value: String,
+) -> impl IntoView {
     let hash = tx.hash.clone();
     let from = tx.from.clone();
-    let block_number = tx.block_number;
+    let block_number = tx.block_number.map(|n| n.to_string()).unwrap_or_default();
     let link = format!("/{}/transactions/{}", chain_id, hash);
     view! {
         <A href=link>
@@ -224,16 +429,20 @@ pub fn TransactionDetails(tx: Transaction, idx: usize, chain_id: u64) -> impl In
                 </div>
                 <div style="flex:1;">
                     <div style="color:#6b7280; font-size:12px; margin-bottom:4px;">{"Hash"}</div>
-                    <div style="font-size:12px; font-family:monospace; word-break:break-all;">
-                        {hash}
+                    <div>
+                        <HashDisplay value=hash />
                     </div>
                     <div style="color:#6b7280; font-size:12px; margin-top:8px;">{"From"}</div>
-                    <div style="font-size:12px; font-family:monospace; word-break:break-all;">
-                        {from}
+                    <div>
+                        <HashDisplay value=from />
                     </div>
                 </div>
                 <div style="text-align:right; min-width:110px;">
-                    <div style="color:#6b7280; font-size:12px;">{"Block"}</div>
+                    <div style="color:#6b7280; font-size:12px;">{"Value"}</div>
+                    <div style="font-size:14px; font-family:monospace;" title=format!("{} wei", tx.value)>
+                        {value}
+                    </div>
+                    <div style="color:#6b7280; font-size:12px; margin-top:8px;">{"Block"}</div>
                     <div style="font-size:14px; font-family:monospace;">{block_number}</div>
                 </div>
             </div>
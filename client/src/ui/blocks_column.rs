@@ -1,9 +1,27 @@
+use crate::ui::hash_display::HashDisplay;
 use js_sys::Date;
 use leptos::prelude::*;
 use leptos_router::components::A;
 use shared::types::block::Block;
 
-fn format_timestamp(timestamp: u64) -> String {
+/// Blocks a column keeps, older ones are dropped as new ones arrive
+pub const MAX_BLOCKS: usize = 200;
+
+/// Adds `incoming` to `blocks`, newest first. A block number seen before is
+/// replaced rather than repeated, so stream reconnects and backfills can
+/// overlap and a re-mined fork tip shows its new hash.
+pub fn merge_blocks(blocks: &mut Vec<Block>, incoming: impl IntoIterator<Item = Block>) {
+    for block in incoming {
+        match blocks.iter_mut().find(|b| b.number == block.number) {
+            Some(known) => *known = block,
+            None => blocks.push(block),
+        }
+    }
+    blocks.sort_by_key(|b| std::cmp::Reverse(b.number));
+    blocks.truncate(MAX_BLOCKS);
+}
+
+pub fn format_timestamp(timestamp: u64) -> String {
     let date = Date::new(&wasm_bindgen::JsValue::from_f64((timestamp * 1000) as f64));
     format!(
         "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
@@ -16,16 +34,13 @@ fn format_timestamp(timestamp: u64) -> String {
     )
 }
 
-fn truncate_hash(hash: &str, len: usize) -> String {
-    if hash.len() <= len {
-        hash.to_string()
-    } else {
-        format!("{}...{}", &hash[..len / 2], &hash[hash.len() - len / 2..])
-    }
-}
-
 #[component]
-pub fn BlocksColumn(blocks: ReadSignal<Vec<Block>>, chainid: u64) -> impl IntoView {
+pub fn BlocksColumn(
+    blocks: ReadSignal<Vec<Block>>,
+    chainid: u64,
+    /// Tag each block as local or inherited from the fork
+    forked: bool,
+) -> impl IntoView {
     view! {
         <div style="flex:1; background:#0b1020; color:#e5e7eb; font-family: ui-monospace, SFMono-Regular, Menlo, Monaco, Consolas, Liberation Mono, monospace; font-size:12px; padding:8px; overflow:auto;">
             <For
@@ -52,11 +67,26 @@ pub fn BlocksColumn(blocks: ReadSignal<Vec<Block>>, chainid: u64) -> impl IntoVi
                                         <div style="display:flex; align-items:center; gap:8px;">
                                             <span style="color:#9ca3af; font-weight:600;">Block:</span>
                                             <span style="color:#60a5fa;">{block.number}</span>
+                                            {forked
+                                                .then(|| {
+                                                    let (label, color) = if block.is_local {
+                                                        ("local", "#34d399")
+                                                    } else {
+                                                        ("forked", "#fbbf24")
+                                                    };
+                                                    view! {
+                                                        <span style=format!(
+                                                            "font-size:10px; padding:0 6px; border:1px solid {}; color:{}; border-radius:9999px;",
+                                                            color,
+                                                            color,
+                                                        )>{label}</span>
+                                                    }
+                                                })}
                                         </div>
                                         <div style="display:flex; align-items:center; gap:8px;">
                                             <span style="color:#9ca3af; font-weight:600;">Hash:</span>
-                                            <span style="color:#e5e7eb; font-family:monospace; font-size:11px;">
-                                                {truncate_hash(&block.hash, 16)}
+                                            <span style="color:#e5e7eb;">
+                                                <HashDisplay value=block.hash.clone() len=16 font_size=11 />
                                             </span>
                                         </div>
                                         <div style="display:flex; align-items:center; gap:8px;">
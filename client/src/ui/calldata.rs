@@ -0,0 +1,128 @@
+/// ABI type of a decoded argument
+#[derive(Clone, Copy)]
+enum Arg {
+    Address,
+    Uint,
+    Bool,
+    /// Dynamic `bytes`, only its presence is shown
+    Bytes,
+}
+
+/// 4-byte selector as hex, function name and named arguments
+type Selector = (&'static str, &'static str, &'static [(&'static str, Arg)]);
+
+/// Common ERC-20, ERC-721 and WETH calls
+const SELECTORS: &[Selector] = &[
+    (
+        "a9059cbb",
+        "transfer",
+        &[("to", Arg::Address), ("amount", Arg::Uint)],
+    ),
+    (
+        "095ea7b3",
+        "approve",
+        &[("spender", Arg::Address), ("amount", Arg::Uint)],
+    ),
+    (
+        "23b872dd",
+        "transferFrom",
+        &[
+            ("from", Arg::Address),
+            ("to", Arg::Address),
+            ("amount", Arg::Uint),
+        ],
+    ),
+    (
+        "42842e0e",
+        "safeTransferFrom",
+        &[
+            ("from", Arg::Address),
+            ("to", Arg::Address),
+            ("tokenId", Arg::Uint),
+        ],
+    ),
+    (
+        "b88d4fde",
+        "safeTransferFrom",
+        &[
+            ("from", Arg::Address),
+            ("to", Arg::Address),
+            ("tokenId", Arg::Uint),
+            ("data", Arg::Bytes),
+        ],
+    ),
+    (
+        "a22cb465",
+        "setApprovalForAll",
+        &[("operator", Arg::Address), ("approved", Arg::Bool)],
+    ),
+    (
+        "40c10f19",
+        "mint",
+        &[("to", Arg::Address), ("amount", Arg::Uint)],
+    ),
+    ("42966c68", "burn", &[("amount", Arg::Uint)]),
+    ("70a08231", "balanceOf", &[("owner", Arg::Address)]),
+    ("d0e30db0", "deposit", &[]),
+    ("2e1a7d4d", "withdraw", &[("amount", Arg::Uint)]),
+];
+
+fn parse_hex(hex: &str) -> Option<Vec<u8>> {
+    let hex = hex.strip_prefix("0x").unwrap_or(hex);
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn format_arg(word: &[u8], arg: Arg) -> Option<String> {
+    match arg {
+        Arg::Address => word[..12]
+            .iter()
+            .all(|b| *b == 0)
+            .then(|| format!("0x{}", to_hex(&word[12..]))),
+        // decimal when it fits a u128, which covers any realistic amount
+        Arg::Uint => Some(match word[..16].iter().all(|b| *b == 0) {
+            true => u128::from_be_bytes(word[16..].try_into().ok()?).to_string(),
+            false => format!("0x{}", to_hex(word)),
+        }),
+        Arg::Bool => match (word[..31].iter().all(|b| *b == 0), word[31]) {
+            (true, 0) => Some("false".to_string()),
+            (true, 1) => Some("true".to_string()),
+            _ => None,
+        },
+        Arg::Bytes => Some("bytes".to_string()),
+    }
+}
+
+/// Renders calldata of a known selector as `transfer(to: 0x…, amount: 1)`.
+/// `None` for unknown selectors or arguments that don't decode, callers
+/// show the raw hex instead.
+pub fn decode_call(input: &str) -> Option<String> {
+    let data = parse_hex(input)?;
+    if data.len() < 4 {
+        return None;
+    }
+    let selector = to_hex(&data[..4]);
+    let (_, name, args) = SELECTORS.iter().find(|(s, _, _)| *s == selector)?;
+    let words = &data[4..];
+    if words.len() < args.len() * 32 {
+        return None;
+    }
+    let rendered = args
+        .iter()
+        .enumerate()
+        .map(|(i, (arg_name, arg))| {
+            format_arg(&words[i * 32..(i + 1) * 32], *arg)
+                .map(|value| format!("{}: {}", arg_name, value))
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(format!("{}({})", name, rendered.join(", ")))
+}
@@ -0,0 +1,232 @@
+use crate::api::client::Api;
+use crate::ui::blocks_column::format_timestamp;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use leptos_router::components::A;
+use leptos_router::hooks::{use_navigate, use_params_map};
+use shared::types::block::Block;
+
+/// Signed difference `b - a`, empty when equal
+fn delta(a: u64, b: u64, unit: &str) -> String {
+    match b.cmp(&a) {
+        std::cmp::Ordering::Equal => String::new(),
+        std::cmp::Ordering::Greater => format!("+{}{}", b - a, unit),
+        std::cmp::Ordering::Less => format!("-{}{}", a - b, unit),
+    }
+}
+
+/// Field name, both values, the difference when numeric, and whether the
+/// row counts as a difference. Number and hash always differ and are
+/// never highlighted.
+fn rows(a: &Block, b: &Block) -> Vec<(&'static str, String, String, String, bool)> {
+    vec![
+        (
+            "Block Number",
+            a.number.to_string(),
+            b.number.to_string(),
+            delta(a.number, b.number, ""),
+            false,
+        ),
+        ("Hash", a.hash.clone(), b.hash.clone(), String::new(), false),
+        (
+            "Timestamp",
+            format_timestamp(a.time),
+            format_timestamp(b.time),
+            delta(a.time, b.time, "s"),
+            false,
+        ),
+        (
+            "Beneficiary",
+            a.beneficiary.clone(),
+            b.beneficiary.clone(),
+            String::new(),
+            a.beneficiary != b.beneficiary,
+        ),
+        (
+            "Gas Used",
+            a.gas_used.to_string(),
+            b.gas_used.to_string(),
+            delta(a.gas_used, b.gas_used, ""),
+            a.gas_used != b.gas_used,
+        ),
+        (
+            "Gas Limit",
+            a.gas_limit.to_string(),
+            b.gas_limit.to_string(),
+            delta(a.gas_limit, b.gas_limit, ""),
+            a.gas_limit != b.gas_limit,
+        ),
+        (
+            "Transaction Count",
+            a.transactions.to_string(),
+            b.transactions.to_string(),
+            delta(a.transactions, b.transactions, ""),
+            a.transactions != b.transactions,
+        ),
+        (
+            "Nonce",
+            a.nonce.clone(),
+            b.nonce.clone(),
+            String::new(),
+            a.nonce != b.nonce,
+        ),
+        (
+            "Mined Locally",
+            a.is_local.to_string(),
+            b.is_local.to_string(),
+            String::new(),
+            a.is_local != b.is_local,
+        ),
+    ]
+}
+
+#[component]
+pub fn ComparePage() -> impl IntoView {
+    let params = use_params_map();
+    let chain_id = move || {
+        params
+            .get()
+            .get("chainid")
+            .and_then(|v| v.parse::<u64>().ok())
+    };
+    let numbers = move || {
+        let params = params.get();
+        let a = params.get("a").and_then(|v| v.parse::<u64>().ok())?;
+        let b = params.get("b").and_then(|v| v.parse::<u64>().ok())?;
+        Some((a, b))
+    };
+    let navigate = use_navigate();
+
+    let (blocks, set_blocks) = signal::<Option<(Block, Block)>>(None);
+    let (loading, set_loading) = signal(false);
+    let (error_msg, set_error_msg) = signal::<Option<String>>(None);
+
+    Effect::new(move |_| {
+        if let (Some(cid), Some((a, b))) = (chain_id(), numbers()) {
+            set_loading.set(true);
+            set_error_msg.set(None);
+            set_blocks.set(None);
+            let api = Api::instance();
+            spawn_local(async move {
+                let (a, b) =
+                    futures_util::future::join(api.get_block(cid, a), api.get_block(cid, b)).await;
+                match a.and_then(|a| b.map(|b| (a.block, b.block))) {
+                    Ok(pair) => set_blocks.set(Some(pair)),
+                    Err(e) => set_error_msg.set(Some(e.to_string())),
+                }
+                set_loading.set(false);
+            });
+        }
+    });
+
+    view! {
+        <div style="font-family: system-ui, -apple-system, Segoe UI, Roboto, Ubuntu, Cantarell, Noto Sans, Helvetica, Arial, Apple Color Emoji, Segoe UI Emoji; padding:16px;">
+            <div style="display:flex; gap:8px; margin-bottom:16px;">
+                <button
+                    on:click=move |_| navigate("/", Default::default())
+                    style="background:#2563eb; color:white; border:none; padding:8px 12px; border-radius:6px; cursor:pointer;"
+                >
+                    {"← Back"}
+                </button>
+            </div>
+            {move || {
+                match (chain_id(), numbers()) {
+                    (Some(cid), Some((a, b))) => {
+                        if loading.get() {
+                            view! {
+                                <div style="background:white; border:1px solid #e5e7eb; border-radius:8px; padding:16px; text-align:center;">
+                                    <div style="color:#6b7280;">{"Loading blocks..."}</div>
+                                </div>
+                            }
+                                .into_any()
+                        } else if let Some(err) = error_msg.get() {
+                            view! {
+                                <div style="padding:16px; color:#842029; background:#f8d7da; border:1px solid #f5c2c7; border-radius:6px;">
+                                    <strong>{"Error: "}</strong>
+                                    {err}
+                                </div>
+                            }
+                                .into_any()
+                        } else if let Some((block_a, block_b)) = blocks.get() {
+                            let differences = rows(&block_a, &block_b)
+                                .iter()
+                                .filter(|row| row.4)
+                                .count();
+                            view! {
+                                <div style="background:white; border:1px solid #e5e7eb; border-radius:8px; padding:16px;">
+                                    <h1 style="font-size:24px; font-weight:600; margin-bottom:4px;">
+                                        {format!("Block {} vs {}", a, b)}
+                                    </h1>
+                                    <div style="color:#6b7280; font-size:13px; margin-bottom:16px;">
+                                        {match differences {
+                                            0 => "No differences".to_string(),
+                                            1 => "1 field differs".to_string(),
+                                            n => format!("{} fields differ", n),
+                                        }}
+                                    </div>
+                                    <table style="width:100%; border-collapse:collapse; font-size:14px;">
+                                        <thead>
+                                            <tr style="text-align:left; color:#6b7280; font-size:12px;">
+                                                <th style="padding:6px 8px;">{"Field"}</th>
+                                                <th style="padding:6px 8px;">
+                                                    <A href=format!("/{}/{}", cid, a)>{format!("Block {}", a)}</A>
+                                                </th>
+                                                <th style="padding:6px 8px;">
+                                                    <A href=format!("/{}/{}", cid, b)>{format!("Block {}", b)}</A>
+                                                </th>
+                                                <th style="padding:6px 8px;">{"Δ"}</th>
+                                            </tr>
+                                        </thead>
+                                        <tbody>
+                                            {rows(&block_a, &block_b)
+                                                .into_iter()
+                                                .map(|(field, value_a, value_b, delta, differs)| {
+                                                    let background = if differs { "#fef3c7" } else { "transparent" };
+                                                    view! {
+                                                        <tr style=format!(
+                                                            "border-top:1px solid #e5e7eb; background:{};",
+                                                            background,
+                                                        )>
+                                                            <td style="padding:6px 8px; color:#6b7280; font-weight:600;">
+                                                                {field}
+                                                            </td>
+                                                            <td style="padding:6px 8px; font-family:monospace; word-break:break-all;">
+                                                                {value_a}
+                                                            </td>
+                                                            <td style="padding:6px 8px; font-family:monospace; word-break:break-all;">
+                                                                {value_b}
+                                                            </td>
+                                                            <td style="padding:6px 8px; font-family:monospace; color:#374151;">
+                                                                {delta}
+                                                            </td>
+                                                        </tr>
+                                                    }
+                                                })
+                                                .collect_view()}
+                                        </tbody>
+                                    </table>
+                                </div>
+                            }
+                                .into_any()
+                        } else {
+                            view! {
+                                <div style="background:white; border:1px solid #e5e7eb; border-radius:8px; padding:16px;">
+                                    <div style="color:#6b7280;">{"No block data available"}</div>
+                                </div>
+                            }
+                                .into_any()
+                        }
+                    }
+                    _ => {
+                        view! {
+                            <div style="padding:16px; color:#842029; background:#f8d7da; border:1px solid #f5c2c7; border-radius:6px;">
+                                {"Invalid chain ID or block numbers"}
+                            </div>
+                        }
+                            .into_any()
+                    }
+                }
+            }}
+        </div>
+    }
+}
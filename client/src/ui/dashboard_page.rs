@@ -0,0 +1,271 @@
+use crate::api::client::Api;
+use crate::status_label;
+use futures_util::{pin_mut, StreamExt};
+use leptos::task::spawn_local;
+use leptos::{leptos_dom::logging::console_error, prelude::*};
+use leptos_router::hooks::use_navigate;
+use shared::types::chain_config::{ChainConfig, ChainStatus};
+use shared::types::chain_status_event::ChainStatusEvent;
+use shared::types::chains_summary::ChainsSummary;
+use std::time::Duration;
+
+/// How often the uptime column ticks
+const UPTIME_TICK: Duration = Duration::from_secs(1);
+
+/// "1h 02m 03s", dropping leading zero units
+fn format_uptime(secs: u64) -> String {
+    let (h, m, s) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if h > 0 {
+        format!("{}h {:02}m {:02}s", h, m, s)
+    } else if m > 0 {
+        format!("{}m {:02}s", m, s)
+    } else {
+        format!("{}s", s)
+    }
+}
+
+fn unix_now() -> u64 {
+    (js_sys::Date::now() / 1000.0) as u64
+}
+
+/// One table row per chain, for setups with more chains than fit as columns
+#[component]
+pub fn DashboardPage() -> impl IntoView {
+    let navigate = use_navigate();
+    let (summary, set_summary) = signal::<Option<ChainsSummary>>(None);
+    let chains = Memo::new(move |_| {
+        let mut chains = summary.get().map(|s| s.chains).unwrap_or_default();
+        chains.sort_by_key(|c| !c.pinned);
+        chains
+    });
+    let (error_msg, set_error_msg) = signal::<Option<String>>(None);
+    let (read_only, set_read_only) = signal(false);
+    let (now, set_now) = signal(unix_now());
+
+    let refresh = move || {
+        spawn_local(async move {
+            match Api::instance().chains_summary().await {
+                Ok(s) => set_summary.set(Some(s)),
+                Err(e) => set_error_msg.set(Some(e.to_string())),
+            }
+        });
+    };
+
+    Effect::new(move |_| refresh());
+    Effect::new(move |_| {
+        spawn_local(async move {
+            if let Ok(health) = Api::instance().health().await {
+                set_read_only.set(health.read_only);
+            }
+        });
+    });
+    Effect::new(move |_| {
+        let title = match summary.get() {
+            Some(s) => format!("LocalChain ({}/{} running)", s.running, s.total),
+            None => "LocalChain".to_string(),
+        };
+        gloo::utils::document().set_title(&title);
+    });
+    on_cleanup(|| gloo::utils::document().set_title("LocalChain"));
+    if let Ok(handle) = set_interval_with_handle(move || set_now.set(unix_now()), UPTIME_TICK) {
+        on_cleanup(move || handle.clear());
+    }
+
+    let on_action = move |id: u64, action: &'static str| {
+        set_error_msg.set(None);
+        spawn_local(async move {
+            if let Err(e) = Api::instance().post_action(&id, action).await {
+                set_error_msg.set(Some(e.to_string()));
+            }
+            refresh();
+        });
+    };
+
+    view! {
+        <div style="font-family: system-ui, -apple-system, Segoe UI, Roboto, Ubuntu, Cantarell, Noto Sans, Helvetica, Arial, Apple Color Emoji, Segoe UI Emoji; padding:16px;">
+            <div style="margin-bottom:16px;">
+                <button
+                    on:click=move |_| navigate("/", Default::default())
+                    style="background:#2563eb; color:white; border:none; padding:8px 12px; border-radius:6px; cursor:pointer;"
+                >
+                    {"← Back"}
+                </button>
+            </div>
+            <div style="background:white; border:1px solid #e5e7eb; border-radius:8px; padding:16px;">
+                <h1 style="font-size:24px; font-weight:600; margin-bottom:4px;">{"Dashboard"}</h1>
+                <div style="color:#6b7280; font-size:14px; margin-bottom:16px;">
+                    {move || {
+                        summary
+                            .get()
+                            .map(|s| {
+                                format!(
+                                    "{} chains: {} running, {} starting, {} stopped, {} error",
+                                    s.total,
+                                    s.running,
+                                    s.starting,
+                                    s.stopped,
+                                    s.error,
+                                )
+                            })
+                    }}
+                </div>
+                {move || {
+                    error_msg
+                        .get()
+                        .map(|e| {
+                            view! {
+                                <div style="margin-bottom:12px; padding:8px; color:#842029; background:#f8d7da; border:1px solid #f5c2c7; border-radius:6px;">
+                                    {e}
+                                </div>
+                            }
+                        })
+                }}
+                <table style="width:100%; border-collapse:collapse; font-size:14px;">
+                    <thead>
+                        <tr style="text-align:left; color:#6b7280; border-bottom:1px solid #e5e7eb;">
+                            <th style="padding:8px;">{"Name"}</th>
+                            <th style="padding:8px;">{"Status"}</th>
+                            <th style="padding:8px;">{"Block"}</th>
+                            <th style="padding:8px;">{"Uptime"}</th>
+                            <th style="padding:8px;">{"Port"}</th>
+                            <th style="padding:8px;"></th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        <For
+                            each=move || chains.get()
+                            key=|c| c.id
+                            children=move |c: ChainConfig| {
+                                let id = c.id;
+                                let chain = Signal::derive(move || {
+                                    chains
+                                        .get()
+                                        .into_iter()
+                                        .find(|c| c.id == id)
+                                        .unwrap_or_else(|| c.clone())
+                                });
+                                view! {
+                                    <DashboardRow
+                                        chain=chain
+                                        now=now
+                                        read_only=read_only
+                                        on_action=move |action| on_action(id, action)
+                                        on_status_change=refresh
+                                    />
+                                }
+                            }
+                        />
+                    </tbody>
+                </table>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn DashboardRow(
+    chain: Signal<ChainConfig>,
+    now: ReadSignal<u64>,
+    read_only: ReadSignal<bool>,
+    on_action: impl Fn(&'static str) + Copy + 'static,
+    /// Called when the stream reports a status the listed config doesn't
+    /// have yet, so the row's uptime can be reloaded
+    on_status_change: impl Fn() + Copy + 'static,
+) -> impl IntoView {
+    let id = chain.get_untracked().id;
+    let (live, set_live) = signal::<Option<ChainStatusEvent>>(None);
+
+    Effect::new(move |_| {
+        spawn_local(async move {
+            match Api::instance().status_stream(id) {
+                Ok(mut es) => {
+                    let events = es.subscribe("message").unwrap();
+                    pin_mut!(events);
+
+                    while let Some(Ok((_event_type, msg))) = events.next().await {
+                        match msg
+                            .data()
+                            .as_string()
+                            .map(|data| serde_json::from_str::<ChainStatusEvent>(&data))
+                        {
+                            Some(Ok(event)) => {
+                                if event.status != chain.get_untracked().status {
+                                    on_status_change();
+                                }
+                                set_live.set(Some(event));
+                            }
+                            _ => console_error(
+                                format!("Error reading status event: {:?}", msg).as_ref(),
+                            ),
+                        }
+                    }
+                }
+                Err(e) => {
+                    console_error(format!("Error reading SSE message: {:?}", e).as_ref());
+                }
+            }
+        });
+    });
+
+    let status = move || {
+        live.get()
+            .map(|e| e.status)
+            .unwrap_or_else(|| chain.get().status)
+    };
+    let status_text = move || status_label(status());
+    let block = move || {
+        live.get()
+            .and_then(|e| e.latest_block)
+            .filter(|_| status() == ChainStatus::Running)
+            .map(|n| format!("#{}", n))
+            .unwrap_or_else(|| "-".to_string())
+    };
+    let uptime = move || {
+        chain
+            .get()
+            .started_at
+            .filter(|_| status() == ChainStatus::Running)
+            .map(|t| format_uptime(now.get().saturating_sub(t)))
+            .unwrap_or_else(|| "-".to_string())
+    };
+    let can_start = move || matches!(status(), ChainStatus::Stopped | ChainStatus::Error);
+    let can_stop = move || matches!(status(), ChainStatus::Running);
+
+    view! {
+        <tr style="border-bottom:1px solid #f3f4f6;">
+            <td style="padding:8px; font-weight:600;">{move || chain.get().name}</td>
+            <td style="padding:8px;">{status_text}</td>
+            <td style="padding:8px; font-family:monospace;">{block}</td>
+            <td style="padding:8px;">{uptime}</td>
+            <td style="padding:8px; font-family:monospace;">{move || chain.get().port}</td>
+            <td style=move || {
+                format!(
+                    "padding:8px; display:{}; gap:6px; justify-content:flex-end;",
+                    if read_only.get() { "none" } else { "flex" },
+                )
+            }>
+                <button
+                    disabled=move || !can_start()
+                    on:click=move |_| on_action("start")
+                    style="padding:4px 8px; border:1px solid #d1d5db; background:white; border-radius:6px; cursor:pointer;"
+                >
+                    {"Start"}
+                </button>
+                <button
+                    disabled=move || !can_stop()
+                    on:click=move |_| on_action("stop")
+                    style="padding:4px 8px; border:1px solid #d1d5db; background:white; border-radius:6px; cursor:pointer;"
+                >
+                    {"Stop"}
+                </button>
+                <button
+                    disabled=move || !can_stop()
+                    on:click=move |_| on_action("restart")
+                    style="padding:4px 8px; border:1px solid #d1d5db; background:white; border-radius:6px; cursor:pointer;"
+                >
+                    {"Restart"}
+                </button>
+            </td>
+        </tr>
+    }
+}
@@ -0,0 +1,17 @@
+use gloo::file::{Blob, ObjectUrl};
+use gloo::utils::document;
+use wasm_bindgen::JsCast;
+
+/// Saves `contents` as a file through a temporary blob URL
+pub fn download_file(filename: &str, mime_type: &str, contents: &str) {
+    let blob = Blob::new_with_options(contents, Some(mime_type));
+    let url = ObjectUrl::from(blob);
+    let Ok(link) = document().create_element("a") else {
+        return;
+    };
+    let _ = link.set_attribute("href", &url);
+    let _ = link.set_attribute("download", filename);
+    if let Ok(link) = link.dyn_into::<web_sys::HtmlElement>() {
+        link.click();
+    }
+}
@@ -0,0 +1,283 @@
+use crate::api::client::Api;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use leptos_router::components::A;
+use leptos_router::hooks::{use_navigate, use_params_map, use_query_map};
+use shared::types::log::Log;
+use shared::types::log_filter::LogFilter;
+use shared::types::logs_response::LogsResponse;
+
+#[component]
+pub fn EventsPage() -> impl IntoView {
+    let params = use_params_map();
+    let query = use_query_map();
+    let chain_id = move || {
+        params
+            .get()
+            .get("chainid")
+            .and_then(|v| v.parse::<u64>().ok())
+    };
+    // the filter lives in the URL so a search can be shared as a link
+    let filter = move || {
+        let query = query.get();
+        LogFilter {
+            address: query.get("address"),
+            topic0: query.get("topic0"),
+            topic1: query.get("topic1"),
+            topic2: query.get("topic2"),
+            topic3: query.get("topic3"),
+            from_block: query.get("from_block").and_then(|v| v.parse().ok()),
+            to_block: query.get("to_block").and_then(|v| v.parse().ok()),
+            page: query.get("page").and_then(|v| v.parse().ok()),
+            page_size: query.get("page_size").and_then(|v| v.parse().ok()),
+        }
+    };
+    let navigate = use_navigate();
+
+    let initial = untrack(filter);
+    let (topics, set_topics) = signal(
+        initial
+            .topics()
+            .map(|t| t.clone().unwrap_or_default())
+            .to_vec(),
+    );
+    let (address, set_address) = signal(initial.address.unwrap_or_default());
+
+    let (logs, set_logs) = signal::<Option<LogsResponse>>(None);
+    let (loading, set_loading) = signal(false);
+    let (error_msg, set_error_msg) = signal::<Option<String>>(None);
+
+    Effect::new(move |_| {
+        if let Some(cid) = chain_id() {
+            let filter = filter();
+            set_loading.set(true);
+            set_error_msg.set(None);
+            let api = Api::instance();
+            spawn_local(async move {
+                match api.get_logs(cid, &filter).await {
+                    Ok(data) => set_logs.set(Some(data)),
+                    Err(e) => {
+                        set_error_msg.set(Some(e.to_string()));
+                        set_logs.set(None);
+                    }
+                }
+                set_loading.set(false);
+            });
+        }
+    });
+
+    let go_to = {
+        let navigate = navigate.clone();
+        move |filter: LogFilter| {
+            if let Some(cid) = chain_id() {
+                navigate(
+                    format!("/{}/events?{}", cid, filter.to_query()).as_str(),
+                    Default::default(),
+                );
+            }
+        }
+    };
+
+    let search = {
+        let go_to = go_to.clone();
+        move |_| {
+            let topics = topics.get();
+            let topic = |i: usize| Some(topics[i].clone()).filter(|t| !t.trim().is_empty());
+            go_to(LogFilter {
+                address: Some(address.get()).filter(|a| !a.trim().is_empty()),
+                topic0: topic(0),
+                topic1: topic(1),
+                topic2: topic(2),
+                topic3: topic(3),
+                page: None,
+                ..filter()
+            });
+        }
+    };
+
+    let go_to_page = move |page: u64| {
+        go_to(LogFilter {
+            page: Some(page),
+            ..filter()
+        })
+    };
+    let go_to_prev = go_to_page.clone();
+    let go_to_next = go_to_page.clone();
+
+    view! {
+        <div style="font-family: system-ui, -apple-system, Segoe UI, Roboto, Ubuntu, Cantarell, Noto Sans, Helvetica, Arial, Apple Color Emoji, Segoe UI Emoji; padding:16px;">
+            <div style="margin-bottom:16px;">
+                <button
+                    on:click=move |_| navigate("/", Default::default())
+                    style="background:#2563eb; color:white; border:none; padding:8px 12px; border-radius:6px; cursor:pointer;"
+                >
+                    {"← Back"}
+                </button>
+            </div>
+            <div style="display:flex; flex-direction:column; gap:16px;">
+                <div style="background:white; border:1px solid #e5e7eb; border-radius:8px; padding:16px;">
+                    <h1 style="font-size:24px; font-weight:600; margin-bottom:16px;">{"Events"}</h1>
+                    <div style="display:flex; flex-direction:column; gap:8px;">
+                        <label>
+                            Contract Address
+                            <input
+                                prop:value=move || address.get()
+                                on:input=move |ev| set_address.set(event_target_value(&ev))
+                                placeholder="0x..."
+                                style="width:100%; padding:6px; border:1px solid #e5e7eb; border-radius:6px; font-family:monospace;"
+                            />
+                        </label>
+                        {(0..4)
+                            .map(|i| {
+                                view! {
+                                    <label>
+                                        {format!("Topic {}", i)}
+                                        <input
+                                            prop:value=move || topics.get()[i].clone()
+                                            on:input=move |ev| {
+                                                set_topics.update(|t| t[i] = event_target_value(&ev))
+                                            }
+                                            placeholder="0x..."
+                                            style="width:100%; padding:6px; border:1px solid #e5e7eb; border-radius:6px; font-family:monospace;"
+                                        />
+                                    </label>
+                                }
+                            })
+                            .collect_view()}
+                    </div>
+                    <div style="display:flex; justify-content:flex-end; margin-top:12px;">
+                        <button
+                            on:click=search
+                            style="background:#2563eb; color:white; border:none; padding:8px 12px; border-radius:6px; cursor:pointer;"
+                        >
+                            {"Search"}
+                        </button>
+                    </div>
+                </div>
+                {move || {
+                    if chain_id().is_none() {
+                        view! {
+                            <div style="padding:16px; color:#842029; background:#f8d7da; border:1px solid #f5c2c7; border-radius:6px;">
+                                {"Invalid chain ID"}
+                            </div>
+                        }
+                            .into_any()
+                    } else if loading.get() {
+                        view! {
+                            <div style="background:white; border:1px solid #e5e7eb; border-radius:8px; padding:16px; text-align:center;">
+                                <div style="color:#6b7280;">{"Loading events..."}</div>
+                            </div>
+                        }
+                            .into_any()
+                    } else if let Some(err) = error_msg.get() {
+                        view! {
+                            <div style="padding:16px; color:#842029; background:#f8d7da; border:1px solid #f5c2c7; border-radius:6px;">
+                                <strong>{"Error: "}</strong>
+                                {err}
+                            </div>
+                        }
+                            .into_any()
+                    } else if let Some(data) = logs.get() {
+                        let cid = chain_id().unwrap_or_default();
+                        let page = data.page;
+                        let pages = data.total.div_ceil(data.page_size).max(1);
+                        let go_to_prev = go_to_prev.clone();
+                        let go_to_next = go_to_next.clone();
+                        view! {
+                            <div style="background:white; border:1px solid #e5e7eb; border-radius:8px; padding:16px;">
+                                <h2 style="font-size:20px; font-weight:600; margin-bottom:16px;">
+                                    {"Matching Events ("}{data.total}{")"}
+                                </h2>
+                                {if data.logs.is_empty() {
+                                    view! {
+                                        <div style="padding:16px; text-align:center; color:#6b7280;">
+                                            {"No events match this filter"}
+                                        </div>
+                                    }
+                                        .into_any()
+                                } else {
+                                    view! {
+                                        <div style="display:flex; flex-direction:column; gap:8px;">
+                                            {data
+                                                .logs
+                                                .into_iter()
+                                                .map(|log| view! { <LogDetails log=log chain_id=cid /> })
+                                                .collect_view()}
+                                        </div>
+                                    }
+                                        .into_any()
+                                }}
+                                <div style="display:flex; align-items:center; justify-content:space-between; margin-top:16px;">
+                                    <button
+                                        disabled={page == 0}
+                                        on:click=move |_| go_to_prev(page.saturating_sub(1))
+                                        style="background:white; border:1px solid #d1d5db; padding:8px 12px; border-radius:6px; cursor:pointer;"
+                                    >
+                                        {"← Prev"}
+                                    </button>
+                                    <div style="color:#6b7280; font-size:14px;">
+                                        {format!("Page {} of {}", page + 1, pages)}
+                                    </div>
+                                    <button
+                                        disabled={page + 1 >= pages}
+                                        on:click=move |_| go_to_next(page + 1)
+                                        style="background:white; border:1px solid #d1d5db; padding:8px 12px; border-radius:6px; cursor:pointer;"
+                                    >
+                                        {"Next →"}
+                                    </button>
+                                </div>
+                            </div>
+                        }
+                            .into_any()
+                    } else {
+                        view! {
+                            <div style="background:white; border:1px solid #e5e7eb; border-radius:8px; padding:16px;">
+                                <div style="color:#6b7280;">{"No event data available"}</div>
+                            </div>
+                        }
+                            .into_any()
+                    }
+                }}
+            </div>
+        </div>
+    }
+}
+
+#[component]
+pub fn LogDetails(log: Log, chain_id: u64) -> impl IntoView {
+    let link = format!("/{}/transactions/{}", chain_id, log.transaction_hash);
+    view! {
+        <A href=link>
+            <div style="text-decoration:none; color:inherit; display:flex; padding:12px; background:#f9fafb; border:1px solid #e5e7eb; border-radius:6px; align-items:flex-start; gap:12px;">
+                <div style="flex:1; min-width:0;">
+                    <div style="color:#6b7280; font-size:12px; margin-bottom:4px;">{"Address"}</div>
+                    <div style="font-size:12px; font-family:monospace; word-break:break-all;">
+                        {log.address}
+                    </div>
+                    <div style="color:#6b7280; font-size:12px; margin-top:8px;">{"Topics"}</div>
+                    {log
+                        .topics
+                        .into_iter()
+                        .map(|topic| {
+                            view! {
+                                <div style="font-size:12px; font-family:monospace; word-break:break-all;">
+                                    {topic}
+                                </div>
+                            }
+                        })
+                        .collect_view()}
+                    <div style="color:#6b7280; font-size:12px; margin-top:8px;">{"Data"}</div>
+                    <div style="font-size:12px; font-family:monospace; word-break:break-all;">
+                        {log.data}
+                    </div>
+                </div>
+                <div style="text-align:right; min-width:110px;">
+                    <div style="color:#6b7280; font-size:12px;">{"Block"}</div>
+                    <div style="font-size:14px; font-family:monospace;">{log.block_number}</div>
+                    <div style="color:#6b7280; font-size:12px; margin-top:8px;">{"Log Index"}</div>
+                    <div style="font-size:14px; font-family:monospace;">{log.log_index}</div>
+                </div>
+            </div>
+        </A>
+    }
+}
@@ -0,0 +1,124 @@
+use gloo::storage::{LocalStorage, Storage};
+use gloo::utils::window;
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// localStorage key of the saved fork endpoints. URLs are kept unmasked,
+/// keys included, and only ever leave the browser as a new chain's fork URL.
+const BOOKMARKS_KEY: &str = "localchain_fork_bookmarks";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct ForkBookmark {
+    name: String,
+    url: String,
+}
+
+fn load() -> Vec<ForkBookmark> {
+    LocalStorage::get(BOOKMARKS_KEY).unwrap_or_default()
+}
+
+fn store(bookmarks: &[ForkBookmark]) {
+    let _ = LocalStorage::set(BOOKMARKS_KEY, bookmarks);
+}
+
+/// Host of the URL, so the suggested name doesn't repeat a key in the path
+fn suggested_name(url: &str) -> String {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    rest.split(['/', '?']).next().unwrap_or(rest).to_string()
+}
+
+/// Dropdown of saved fork URLs for the modal's fork section. Shows names
+/// only, picking one fills the Fork URL field.
+#[component]
+pub fn ForkBookmarks(
+    fork_url: ReadSignal<Option<String>>,
+    set_fork_url: WriteSignal<Option<String>>,
+) -> impl IntoView {
+    let (bookmarks, set_bookmarks) = signal(load());
+    let current_url = move || {
+        fork_url
+            .get()
+            .map(|u| u.trim().to_string())
+            .filter(|u| !u.is_empty())
+    };
+    let selected = move || {
+        current_url()
+            .and_then(|url| bookmarks.get().into_iter().find(|b| b.url == url))
+            .map(|b| b.name)
+    };
+
+    let save = move |_| {
+        let Some(url) = current_url() else {
+            return;
+        };
+        let answer = window()
+            .prompt_with_message_and_default("Bookmark name", &suggested_name(&url))
+            .ok()
+            .flatten();
+        let Some(name) = answer
+            .map(|n| n.trim().to_string())
+            .filter(|n| !n.is_empty())
+        else {
+            return;
+        };
+        set_bookmarks.update(|list| {
+            // replaces a bookmark with the same name or URL
+            list.retain(|b| b.name != name && b.url != url);
+            list.push(ForkBookmark { name, url });
+            store(list);
+        });
+    };
+    let remove = move |_| {
+        if let Some(name) = selected() {
+            set_bookmarks.update(|list| {
+                list.retain(|b| b.name != name);
+                store(list);
+            });
+        }
+    };
+
+    view! {
+        <div style="display:flex; gap:8px; align-items:center;">
+            <select
+                prop:value=move || selected().unwrap_or_default()
+                on:change=move |ev| {
+                    let name = event_target_value(&ev);
+                    if let Some(bookmark) = bookmarks.get_untracked().into_iter().find(|b| b.name == name) {
+                        set_fork_url.set(Some(bookmark.url));
+                    }
+                }
+                disabled=move || bookmarks.get().is_empty()
+                style="flex:1; padding:6px; border:1px solid #e5e7eb; border-radius:6px; background:white;"
+            >
+                <option value="">
+                    {move || if bookmarks.get().is_empty() { "No bookmarks" } else { "Bookmarks…" }}
+                </option>
+                {move || {
+                    bookmarks
+                        .get()
+                        .into_iter()
+                        .map(|b| {
+                            let value = b.name.clone();
+                            view! { <option value=value>{b.name}</option> }
+                        })
+                        .collect_view()
+                }}
+            </select>
+            <button
+                on:click=save
+                disabled=move || current_url().is_none() || selected().is_some()
+                title="Save the current Fork URL in this browser"
+                style="padding:6px 8px; border:1px solid #d1d5db; background:white; border-radius:6px; cursor:pointer;"
+            >
+                {"Save as Bookmark"}
+            </button>
+            <button
+                on:click=remove
+                disabled=move || selected().is_none()
+                style="padding:6px 8px; border:1px solid #d1d5db; background:white; border-radius:6px; cursor:pointer;"
+            >
+                {"Remove"}
+            </button>
+        </div>
+    }
+}
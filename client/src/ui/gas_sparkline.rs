@@ -0,0 +1,71 @@
+use leptos::prelude::*;
+use shared::types::block::Block;
+
+/// Blocks plotted, newest on the right
+const SPARKLINE_BLOCKS: usize = 50;
+
+/// Gas used as a percentage of the limit, 0 for a block without a limit
+fn gas_percent(block: &Block) -> f64 {
+    if block.gas_limit == 0 {
+        0.0
+    } else {
+        block.gas_used as f64 * 100.0 / block.gas_limit as f64
+    }
+}
+
+/// Gas utilization of the most recent blocks as an inline SVG line.
+/// `blocks` is expected newest first, as the block stream keeps it.
+#[component]
+pub fn GasSparkline(blocks: ReadSignal<Vec<Block>>) -> impl IntoView {
+    let recent = move || {
+        let blocks = blocks.get();
+        let mut recent: Vec<f64> = blocks
+            .iter()
+            .take(SPARKLINE_BLOCKS)
+            .map(gas_percent)
+            .collect();
+        recent.reverse();
+        recent
+    };
+    let points = move || {
+        let recent = recent();
+        let step = 100.0 / (recent.len().max(2) - 1) as f64;
+        recent
+            .iter()
+            .enumerate()
+            .map(|(i, pct)| format!("{:.2},{:.2}", i as f64 * step, 100.0 - pct))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    let label = move || {
+        let recent = recent();
+        let avg = recent.iter().sum::<f64>() / recent.len().max(1) as f64;
+        format!(
+            "gas {:.1}% (avg {:.1}% over {} blocks)",
+            recent.last().copied().unwrap_or_default(),
+            avg,
+            recent.len()
+        )
+    };
+
+    view! {
+        <Show when=move || { blocks.with(|b| b.len() >= 2) }>
+            <div style="display:flex; align-items:center; gap:8px; padding:4px 8px; background:#0b1020; color:#9ca3af; font-size:11px; border-bottom:1px solid #1f2937;">
+                <svg
+                    viewBox="0 0 100 100"
+                    preserveAspectRatio="none"
+                    style="flex:1; height:28px;"
+                >
+                    <polyline
+                        points=points
+                        fill="none"
+                        stroke="#34d399"
+                        stroke-width="1.5"
+                        vector-effect="non-scaling-stroke"
+                    />
+                </svg>
+                <span style="white-space:nowrap;">{label}</span>
+            </div>
+        </Show>
+    }
+}
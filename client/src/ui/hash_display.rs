@@ -0,0 +1,69 @@
+use leptos::prelude::*;
+use std::time::Duration;
+
+/// How long the copy button says "Copied"
+const COPIED_FOR: Duration = Duration::from_millis(1500);
+
+/// Keeps `len / 2` characters from each end, e.g. `0x1234...cdef`
+fn truncate_hash(hash: &str, len: usize) -> String {
+    if hash.len() <= len {
+        hash.to_string()
+    } else {
+        format!("{}...{}", &hash[..len / 2], &hash[hash.len() - len / 2..])
+    }
+}
+
+/// A hash or address in monospace with a copy button. With `len` only the
+/// ends are shown and the full value is in the tooltip, without it the
+/// value wraps anywhere. Color is inherited so it fits light and dark panes.
+#[component]
+pub fn HashDisplay(
+    value: String,
+    #[prop(optional)] len: Option<usize>,
+    #[prop(default = 12)] font_size: u32,
+) -> impl IntoView {
+    let (copied, set_copied) = signal(false);
+    let shown = match len {
+        Some(len) => truncate_hash(&value, len),
+        None => value.clone(),
+    };
+    let wrap = if len.is_some() {
+        "white-space:nowrap;"
+    } else {
+        "word-break:break-all;"
+    };
+    let title = value.clone();
+    // also used inside links, a copy must not follow them
+    let copy = move |ev: leptos::ev::MouseEvent| {
+        ev.prevent_default();
+        ev.stop_propagation();
+        let _ = gloo::utils::window()
+            .navigator()
+            .clipboard()
+            .write_text(&value);
+        set_copied.set(true);
+        set_timeout(move || set_copied.set(false), COPIED_FOR);
+    };
+
+    view! {
+        <span style="display:inline-flex; align-items:center; gap:6px; max-width:100%;">
+            <span
+                title=title
+                style=format!(
+                    "font-family:monospace; font-size:{}px; {}",
+                    font_size,
+                    wrap,
+                )
+            >
+                {shown}
+            </span>
+            <button
+                on:click=copy
+                title="Copy to clipboard"
+                style="flex-shrink:0; padding:0 4px; font-size:10px; line-height:16px; border:1px solid currentColor; background:transparent; color:inherit; opacity:0.6; border-radius:4px; cursor:pointer;"
+            >
+                {move || if copied.get() { "Copied" } else { "Copy" }}
+            </button>
+        </span>
+    }
+}
@@ -1,16 +1,207 @@
+use crate::api::client::Api;
+use crate::api::error::ApiError;
+use futures_util::{pin_mut, StreamExt};
+use leptos::html::Div;
+use leptos::leptos_dom::logging::console_error;
 use leptos::prelude::*;
+use leptos::task::spawn_local;
+use shared::types::log_search::LogSearchResponse;
+use std::time::Duration;
+
+/// How often a log view without a log stream fetches new lines
+const LOG_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Appends a chain's log lines to `set_logs`, starting with the last `tail`
+/// buffered ones (all when `None`). Ends once the view owning `set_logs` is
+/// gone.
+pub async fn follow_logs(id: u64, tail: Option<usize>, set_logs: WriteSignal<Vec<String>>) {
+    let mut received = false;
+    match Api::instance().log_stream(id, tail) {
+        Ok(mut es) => {
+            let stdout = es.subscribe("message").unwrap();
+            pin_mut!(stdout);
+
+            while let Some(Ok((_event_type, msg))) = stdout.next().await {
+                received = true;
+                if let Some(msg) = msg.data().as_string() {
+                    if set_logs.try_update(|v| v.push(msg)).is_none() {
+                        return;
+                    }
+                } else {
+                    console_error(format!("Error reading SSE message: {:?}", msg).as_ref());
+                }
+            }
+        }
+        Err(e) => {
+            console_error(format!("Error reading SSE message: {:?}", e).as_ref());
+        }
+    }
+    // a stream that fails before its first line, even the buffered ones,
+    // is blocked rather than interrupted
+    if !received {
+        poll_logs(id, tail, set_logs).await;
+    }
+}
+
+/// Keeps a log view filled where event streams don't get through, e.g.
+/// behind proxies that buffer responses. Also ends once the chain is gone.
+async fn poll_logs(id: u64, tail: Option<usize>, set_logs: WriteSignal<Vec<String>>) {
+    console_error(format!("log stream of chain {} unavailable, polling", id).as_ref());
+    let mut since = None;
+    loop {
+        match Api::instance().logs_since(id, since).await {
+            Ok(page) => {
+                // the first page is the whole buffer, keep what a stream replays
+                let skip = match (since, tail) {
+                    (None, Some(tail)) => page.lines.len().saturating_sub(tail),
+                    _ => 0,
+                };
+                if set_logs.is_disposed() {
+                    return;
+                }
+                if page.lines.len() > skip {
+                    set_logs.update(|v| v.extend(page.lines.into_iter().skip(skip)));
+                }
+                since = Some(page.next_index);
+            }
+            Err(ApiError::Status(..)) => return,
+            // unreachable for now, try again on the next tick
+            Err(_) => {}
+        }
+        gloo::timers::future::sleep(LOG_POLL_INTERVAL).await;
+    }
+}
 
 #[component]
-pub fn LogsColumn(logs: ReadSignal<Vec<String>>) -> impl IntoView {
+pub fn LogsColumn(chain_id: u64, logs: ReadSignal<Vec<String>>) -> impl IntoView {
+    let (query, set_query) = signal(String::new());
+    let (results, set_results) = signal::<Option<Result<LogSearchResponse, String>>>(None);
+    // line jumped to from the results, scrolled into view and outlined
+    let (selected, set_selected) = signal::<Option<String>>(None);
+    // the query the results were fetched for, so typing doesn't re-highlight
+    let (searched, set_searched) = signal(String::new());
+
+    let search = move || {
+        let q = query.get_untracked().trim().to_string();
+        set_selected.set(None);
+        if q.is_empty() {
+            set_results.set(None);
+            set_searched.set(String::new());
+            return;
+        }
+        spawn_local(async move {
+            let found = Api::instance().search_logs(chain_id, &q).await;
+            set_searched.set(q.to_lowercase());
+            set_results.set(Some(found.map_err(|e| e.to_string())));
+        });
+    };
+
     view! {
-        <div style="flex:1; background:#0b1020; color:#e5e7eb; font-family: ui-monospace, SFMono-Regular, Menlo, Monaco, Consolas, Liberation Mono, monospace; font-size:12px; padding:8px; overflow:auto;">
-            <For
-                each=move || logs.get()
-                key=|log| log.clone()
-                children=move |log: String| {
-                    view! { <div>{log}</div> }
-                }
-            />
+        <div style="flex:1; display:flex; flex-direction:column; min-height:0;">
+            <div style="display:flex; gap:6px; padding:6px 8px; background:#111827; border-bottom:1px solid #1f2937;">
+                <input
+                    placeholder="Search log"
+                    prop:value=query
+                    on:input=move |ev| set_query.set(event_target_value(&ev))
+                    on:keydown=move |ev| {
+                        if ev.key() == "Enter" {
+                            search()
+                        }
+                    }
+                    style="flex:1; padding:4px 6px; background:#0b1020; color:#e5e7eb; border:1px solid #374151; border-radius:4px; font-size:12px;"
+                />
+                <button
+                    on:click=move |_| search()
+                    style="padding:4px 8px; border:1px solid #374151; background:#1f2937; color:#e5e7eb; border-radius:4px; cursor:pointer; font-size:12px;"
+                >
+                    {"Search"}
+                </button>
+            </div>
+            {move || {
+                results
+                    .get()
+                    .map(|found| match found {
+                        Ok(found) => {
+                            let shown = found.matches.len();
+                            view! {
+                                <div style="max-height:120px; overflow:auto; background:#111827; color:#9ca3af; font-family: ui-monospace, SFMono-Regular, Menlo, Monaco, Consolas, Liberation Mono, monospace; font-size:11px; padding:4px 8px; border-bottom:1px solid #1f2937;">
+                                    <div>
+                                        {if found.total as usize > shown {
+                                            format!("{} matches, showing the last {}", found.total, shown)
+                                        } else {
+                                            format!("{} matches", found.total)
+                                        }}
+                                    </div>
+                                    {found
+                                        .matches
+                                        .into_iter()
+                                        .map(|m| {
+                                            let line = m.line.clone();
+                                            view! {
+                                                <div
+                                                    on:click=move |_| set_selected.set(Some(line.clone()))
+                                                    title="Jump to line"
+                                                    style="cursor:pointer; white-space:nowrap; overflow:hidden; text-overflow:ellipsis;"
+                                                >
+                                                    <span style="color:#6b7280;">{format!("{:>5} ", m.index)}</span>
+                                                    {m.line}
+                                                </div>
+                                            }
+                                        })
+                                        .collect_view()}
+                                </div>
+                            }
+                                .into_any()
+                        }
+                        Err(e) => {
+                            view! {
+                                <div style="padding:4px 8px; background:#111827; color:#f87171; font-size:11px;">
+                                    {e}
+                                </div>
+                            }
+                                .into_any()
+                        }
+                    })
+            }}
+            <div style="flex:1; background:#0b1020; color:#e5e7eb; font-family: ui-monospace, SFMono-Regular, Menlo, Monaco, Consolas, Liberation Mono, monospace; font-size:12px; padding:8px; overflow:auto;">
+                <For
+                    each=move || logs.get()
+                    key=|log| log.clone()
+                    children=move |log: String| {
+                        let node = NodeRef::<Div>::new();
+                        let is_selected = {
+                            let log = log.clone();
+                            move || selected.with(|s| s.as_ref() == Some(&log))
+                        };
+                        let is_match = {
+                            let log = log.to_lowercase();
+                            move || searched.with(|q| !q.is_empty() && log.contains(q.as_str()))
+                        };
+                        let scroll_to = is_selected.clone();
+                        Effect::new(move |_| {
+                            if scroll_to() {
+                                if let Some(el) = node.get() {
+                                    el.scroll_into_view();
+                                }
+                            }
+                        });
+                        view! {
+                            <div
+                                node_ref=node
+                                style=move || {
+                                    format!(
+                                        "background:{}; outline:{};",
+                                        if is_match() { "#3f3f12" } else { "transparent" },
+                                        if is_selected() { "1px solid #facc15" } else { "none" },
+                                    )
+                                }
+                            >
+                                {log}
+                            </div>
+                        }
+                    }
+                />
+            </div>
         </div>
     }
 }
@@ -0,0 +1,71 @@
+use crate::api::client::Api;
+use crate::ui::download::download_file;
+use crate::ui::logs_column::{follow_logs, LogsColumn};
+use leptos::leptos_dom::logging::console_error;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use leptos_router::hooks::{use_navigate, use_params_map};
+
+/// A chain's log on its own page, the whole buffer followed live
+#[component]
+pub fn LogsPage() -> impl IntoView {
+    let params = use_params_map();
+    let chain_id = move || {
+        params
+            .get()
+            .get("chainid")
+            .and_then(|v| v.parse::<u64>().ok())
+    };
+    let navigate = use_navigate();
+
+    let download = move |_| {
+        if let Some(id) = chain_id() {
+            spawn_local(async move {
+                match Api::instance().download_logs(id).await {
+                    Ok(text) => download_file(&format!("chain-{}.log", id), "text/plain", &text),
+                    Err(e) => console_error(format!("Error downloading log: {}", e).as_ref()),
+                }
+            });
+        }
+    };
+
+    view! {
+        <div style="font-family: system-ui, -apple-system, Segoe UI, Roboto, Ubuntu, Cantarell, Noto Sans, Helvetica, Arial, Apple Color Emoji, Segoe UI Emoji; height:100vh; display:flex; flex-direction:column;">
+            <div style="display:flex; gap:8px; align-items:center; padding:8px 16px; border-bottom:1px solid #e5e7eb;">
+                <button
+                    on:click=move |_| navigate("/", Default::default())
+                    style="background:#2563eb; color:white; border:none; padding:8px 12px; border-radius:6px; cursor:pointer;"
+                >
+                    {"← Back"}
+                </button>
+                <h1 style="flex:1; font-size:18px; font-weight:600; margin:0;">
+                    {move || match chain_id() {
+                        Some(id) => format!("Chain {} Log", id),
+                        None => "Log".to_string(),
+                    }}
+                </h1>
+                <button
+                    on:click=download
+                    style="padding:6px 8px; border:1px solid #d1d5db; background:white; border-radius:6px; cursor:pointer;"
+                >
+                    {"Download Log"}
+                </button>
+            </div>
+            {move || match chain_id() {
+                Some(id) => {
+                    let (logs, set_logs) = signal(Vec::<String>::new());
+                    spawn_local(follow_logs(id, None, set_logs));
+                    view! { <LogsColumn chain_id=id logs=logs /> }.into_any()
+                }
+                None => {
+                    view! {
+                        <div style="margin:16px; padding:16px; color:#842029; background:#f8d7da; border:1px solid #f5c2c7; border-radius:6px;">
+                            {"Invalid chain ID"}
+                        </div>
+                    }
+                        .into_any()
+                }
+            }}
+        </div>
+    }
+}
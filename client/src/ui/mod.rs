@@ -1,4 +1,13 @@
 pub mod block_page;
 pub mod blocks_column;
+pub mod calldata;
+pub mod compare_page;
+pub mod dashboard_page;
+pub mod download;
+pub mod events_page;
+pub mod fork_bookmarks;
+pub mod gas_sparkline;
+pub mod hash_display;
 pub mod logs_column;
+pub mod logs_page;
 pub mod transaction_page;
@@ -1,7 +1,10 @@
 use crate::api::client::Api;
+use crate::ui::calldata::decode_call;
+use crate::ui::hash_display::HashDisplay;
 use leptos::prelude::*;
 use leptos::task::spawn_local;
 use leptos_router::hooks::{use_navigate, use_params_map};
+use shared::types::chain_config::ChainConfig;
 use shared::types::transaction::Transaction;
 
 #[component]
@@ -16,8 +19,15 @@ pub fn TransactionPage() -> impl IntoView {
     let transaction_hash = move || params.get().get("transactionhash");
 
     let (transaction, set_transaction) = signal::<Option<Transaction>>(None);
+    let (confirmations, set_confirmations) = signal(0u64);
+    // for the currency the value is shown in, ETH until it has loaded
+    let (chain, set_chain) = signal(ChainConfig::default());
     let (loading, set_loading) = signal(false);
     let (error_msg, set_error_msg) = signal::<Option<String>>(None);
+    // hashes of the previous and next transactions in the same block
+    let (neighbors, set_neighbors) = signal::<(Option<String>, Option<String>)>((None, None));
+    let (show_trace, set_show_trace) = signal(false);
+    let (trace, set_trace) = signal::<Option<Result<String, String>>>(None);
     let navigation = use_navigate();
     let navigate_home = navigation.clone();
     let navigate_for_branch = navigation.clone();
@@ -27,16 +37,42 @@ pub fn TransactionPage() -> impl IntoView {
             set_loading.set(true);
             set_error_msg.set(None);
             set_transaction.set(None);
+            set_neighbors.set((None, None));
+            set_show_trace.set(false);
+            set_trace.set(None);
             let api = Api::instance();
             let hash_for_fetch = hash.clone();
             spawn_local(async move {
+                if let Ok(config) = api.get_chain(cid).await {
+                    set_chain.set(config);
+                }
                 match api.get_transaction(cid, hash_for_fetch).await {
                     Ok(resp) => {
-                        set_transaction.set(Some(resp.transaction));
+                        let tx = resp.transaction;
+                        let block_number = tx.block_number;
+                        let index = tx.index;
+                        set_confirmations.set(resp.confirmations);
+                        set_transaction.set(Some(tx));
                         set_error_msg.set(None);
+                        let (Some(block_number), Some(index)) = (block_number, index) else {
+                            // pending, there is no block to step through yet
+                            set_loading.set(false);
+                            return;
+                        };
+                        if let Ok(block) = api.get_block(cid, block_number).await {
+                            let hash_at = |i: u64| {
+                                block
+                                    .transactions
+                                    .iter()
+                                    .find(|t| t.index == Some(i))
+                                    .map(|t| t.hash.clone())
+                            };
+                            let prev = index.checked_sub(1).and_then(hash_at);
+                            set_neighbors.set((prev, hash_at(index + 1)));
+                        }
                     }
                     Err(err) => {
-                        set_error_msg.set(Some(err));
+                        set_error_msg.set(Some(err.to_string()));
                         set_transaction.set(None);
                     }
                 }
@@ -45,6 +81,19 @@ pub fn TransactionPage() -> impl IntoView {
         }
     });
 
+    let toggle_trace = move |_| {
+        set_show_trace.update(|v| *v = !*v);
+        if !show_trace.get_untracked() || trace.get_untracked().is_some() {
+            return;
+        }
+        if let (Some(cid), Some(hash)) = (chain_id(), transaction_hash()) {
+            spawn_local(async move {
+                let trace = Api::instance().trace_transaction(cid, &hash).await;
+                set_trace.set(Some(trace.map_err(|e| e.to_string())));
+            });
+        }
+    };
+
     view! {
         <div style="font-family: system-ui, -apple-system, Segoe UI, Roboto, Ubuntu, Cantarell, Noto Sans, Helvetica, Arial, Apple Color Emoji, Segoe UI Emoji; padding:16px;">
             <div style="margin-bottom:16px;">
@@ -75,6 +124,8 @@ pub fn TransactionPage() -> impl IntoView {
                                 .into_any()
                         } else if let Some(tx) = transaction.get() {
                             let navigate_to_block = navigate_for_branch.clone();
+                            let navigate_to_prev = navigate_for_branch.clone();
+                            let navigate_to_next = navigate_for_branch.clone();
                             view! {
                                 <div style="display:flex; flex-direction:column; gap:16px;">
                                     <div style="background:white; border:1px solid #e5e7eb; border-radius:8px; padding:16px;">
@@ -86,24 +137,40 @@ pub fn TransactionPage() -> impl IntoView {
                                                 <div style="color:#6b7280; font-size:12px; margin-bottom:4px;">
                                                     {"Hash"}
                                                 </div>
-                                                <div style="font-size:14px; font-family:monospace; word-break:break-all;">
-                                                    {tx.hash.clone()}
-                                                </div>
+                                                <HashDisplay value=tx.hash.clone() font_size=14 />
                                             </div>
                                             <div style="padding:12px; background:#f9fafb; border-radius:6px;">
                                                 <div style="color:#6b7280; font-size:12px; margin-bottom:4px;">
                                                     {"From"}
                                                 </div>
-                                                <div style="font-size:14px; font-family:monospace; word-break:break-all;">
-                                                    {tx.from.clone()}
-                                                </div>
+                                                <HashDisplay value=tx.from.clone() font_size=14 />
                                             </div>
                                             <div style="padding:12px; background:#f9fafb; border-radius:6px;">
                                                 <div style="color:#6b7280; font-size:12px; margin-bottom:4px;">
                                                     {"Block Number"}
                                                 </div>
                                                 <div style="font-size:14px; font-family:monospace;">
-                                                    {tx.block_number}
+                                                    {match tx.block_number {
+                                                        Some(number) => number.to_string(),
+                                                        None => "Pending".to_string(),
+                                                    }}
+                                                </div>
+                                                <div style="color:#6b7280; font-size:12px; margin-top:4px;">
+                                                    {move || match confirmations.get() {
+                                                        1 => "1 confirmation".to_string(),
+                                                        n => format!("{} confirmations", n),
+                                                    }}
+                                                </div>
+                                            </div>
+                                            <div style="padding:12px; background:#f9fafb; border-radius:6px;">
+                                                <div style="color:#6b7280; font-size:12px; margin-bottom:4px;">
+                                                    {"Value"}
+                                                </div>
+                                                <div
+                                                    style="font-size:14px; font-family:monospace;"
+                                                    title=format!("{} wei", tx.value)
+                                                >
+                                                    {chain.get().format_value(&tx.value)}
                                                 </div>
                                             </div>
                                             <div style="padding:12px; background:#f9fafb; border-radius:6px;">
@@ -111,17 +178,75 @@ pub fn TransactionPage() -> impl IntoView {
                                                     {"Index"}
                                                 </div>
                                                 <div style="font-size:14px; font-family:monospace;">
-                                                    {tx.index}
+                                                    {tx.index.map(|i| i.to_string()).unwrap_or_else(|| "-".to_string())}
                                                 </div>
                                             </div>
                                         </div>
-                                        <div style="display:flex; justify-content:flex-end; margin-top:16px;">
+                                        {(tx.input.len() > 2)
+                                            .then(|| {
+                                                view! {
+                                                    <div style="padding:12px; background:#f9fafb; border-radius:6px; margin-top:12px;">
+                                                        <div style="color:#6b7280; font-size:12px; margin-bottom:4px;">
+                                                            {"Input"}
+                                                        </div>
+                                                        {decode_call(&tx.input)
+                                                            .map(|call| {
+                                                                view! {
+                                                                    <div style="font-size:14px; font-family:monospace; word-break:break-all; margin-bottom:6px;">
+                                                                        {call}
+                                                                    </div>
+                                                                }
+                                                            })}
+                                                        <div style="font-size:12px; font-family:monospace; word-break:break-all; color:#374151; max-height:160px; overflow:auto;">
+                                                            {tx.input.clone()}
+                                                        </div>
+                                                    </div>
+                                                }
+                                            })}
+                                        <div style="display:flex; justify-content:flex-end; gap:8px; margin-top:16px;">
+                                            <button
+                                                disabled=move || neighbors.get().0.is_none()
+                                                on:click=move |_| {
+                                                    if let Some(hash) = neighbors.get().0 {
+                                                        navigate_to_prev(
+                                                            format!("/{}/transactions/{}", cid, hash).as_str(),
+                                                            Default::default(),
+                                                        )
+                                                    }
+                                                }
+                                                style="background:white; border:1px solid #d1d5db; padding:8px 12px; border-radius:6px; cursor:pointer;"
+                                            >
+                                                {"← Prev Tx"}
+                                            </button>
+                                            <button
+                                                disabled=move || neighbors.get().1.is_none()
+                                                on:click=move |_| {
+                                                    if let Some(hash) = neighbors.get().1 {
+                                                        navigate_to_next(
+                                                            format!("/{}/transactions/{}", cid, hash).as_str(),
+                                                            Default::default(),
+                                                        )
+                                                    }
+                                                }
+                                                style="background:white; border:1px solid #d1d5db; padding:8px 12px; border-radius:6px; cursor:pointer;"
+                                            >
+                                                {"Next Tx →"}
+                                            </button>
+                                            <button
+                                                on:click=toggle_trace
+                                                style="background:white; border:1px solid #d1d5db; padding:8px 12px; border-radius:6px; cursor:pointer;"
+                                            >
+                                                {move || if show_trace.get() { "Hide Trace" } else { "Trace" }}
+                                            </button>
                                             <button
+                                                disabled=tx.block_number.is_none()
                                                 on:click=move |_| {
-                                                    navigate_to_block(
-                                                        format!("/{}/{}", cid, tx.block_number).as_str(),
-                                                        Default::default(),
-                                                    )
+                                                    if let Some(number) = tx.block_number {
+                                                        navigate_to_block(
+                                                            format!("/{}/{}", cid, number).as_str(),
+                                                            Default::default(),
+                                                        )
+                                                    }
                                                 }
                                                 style="background:#2563eb; color:white; border:none; padding:8px 12px; border-radius:6px; cursor:pointer;"
                                             >
@@ -129,6 +254,44 @@ pub fn TransactionPage() -> impl IntoView {
                                             </button>
                                         </div>
                                     </div>
+                                    {move || {
+                                        show_trace
+                                            .get()
+                                            .then(|| {
+                                                view! {
+                                                    <div style="background:white; border:1px solid #e5e7eb; border-radius:8px; padding:16px;">
+                                                        <h2 style="font-size:20px; font-weight:600; margin-bottom:16px;">
+                                                            {"Trace"}
+                                                        </h2>
+                                                        {match trace.get() {
+                                                            None => {
+                                                                view! {
+                                                                    <div style="color:#6b7280;">{"Tracing transaction..."}</div>
+                                                                }
+                                                                    .into_any()
+                                                            }
+                                                            Some(Ok(trace)) => {
+                                                                view! {
+                                                                    <pre style="max-height:480px; overflow:auto; font-size:12px; background:#f9fafb; padding:12px; border-radius:6px;">
+                                                                        {trace}
+                                                                    </pre>
+                                                                }
+                                                                    .into_any()
+                                                            }
+                                                            Some(Err(err)) => {
+                                                                view! {
+                                                                    <div style="padding:16px; color:#842029; background:#f8d7da; border:1px solid #f5c2c7; border-radius:6px;">
+                                                                        <strong>{"Error: "}</strong>
+                                                                        {err}
+                                                                    </div>
+                                                                }
+                                                                    .into_any()
+                                                            }
+                                                        }}
+                                                    </div>
+                                                }
+                                            })
+                                    }}
                                 </div>
                             }
                                 .into_any()
@@ -156,4 +319,3 @@ pub fn TransactionPage() -> impl IntoView {
         </div>
     }
 }
-
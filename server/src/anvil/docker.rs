@@ -0,0 +1,66 @@
+use std::net::IpAddr;
+use std::process::Stdio;
+use tokio::process::Command;
+
+/// Image chains with the `docker` runtime run in, overridable with
+/// `LOCALCHAIN_DOCKER_IMAGE`
+const DEFAULT_IMAGE: &str = "ghcr.io/foundry-rs/foundry";
+
+fn image() -> String {
+    std::env::var("LOCALCHAIN_DOCKER_IMAGE")
+        .ok()
+        .filter(|i| !i.is_empty())
+        .unwrap_or_else(|| DEFAULT_IMAGE.to_string())
+}
+
+/// Fixed per chain, so a container left behind by a server that crashed can
+/// be found and removed by the next start
+fn container_name(chain_id: u64) -> String {
+    format!("localchain-{}", chain_id)
+}
+
+/// `docker run` for the prepared `anvil` command, which must listen on all
+/// interfaces inside the container. Its port is published on `host` only, and
+/// its environment is handed through by name so values don't end up on the
+/// logged command line.
+pub fn command(anvil: &Command, chain_id: u64, host: IpAddr, port: u16) -> Command {
+    let anvil = anvil.as_std();
+    let publish = match host {
+        IpAddr::V4(ip) => format!("{}:{}:{}", ip, port, port),
+        IpAddr::V6(ip) => format!("[{}]:{}:{}", ip, port, port),
+    };
+    let mut cmd = Command::new("docker");
+    cmd.arg("run")
+        .arg("--rm")
+        .arg("--name")
+        .arg(container_name(chain_id))
+        .arg("-p")
+        .arg(publish)
+        .arg("--entrypoint")
+        .arg("anvil");
+    for (key, value) in anvil.get_envs() {
+        if let Some(value) = value {
+            cmd.arg("-e").arg(key).env(key, value);
+        }
+    }
+    cmd.arg(image()).args(anvil.get_args());
+    cmd
+}
+
+/// Stops the chain's container, which `--rm` then removes. Nothing to do
+/// when there is none.
+pub async fn stop(chain_id: u64) -> Result<(), String> {
+    let output = Command::new("docker")
+        .arg("stop")
+        .arg(container_name(chain_id))
+        .stdout(Stdio::null())
+        .output()
+        .await
+        .map_err(|e| format!("could not run docker: {}", e))?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if output.status.success() || stderr.contains("No such container") {
+        Ok(())
+    } else {
+        Err(format!("docker stop failed: {}", stderr.trim()))
+    }
+}
@@ -1 +1,3 @@
+pub mod docker;
 pub mod process;
+pub mod version;
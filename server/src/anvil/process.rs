@@ -1,57 +1,350 @@
+use crate::anvil::docker;
+use crate::anvil::version::AnvilVersion;
+use crate::logs::LogBuffer;
+use crate::rpc::{error_response, INTERNAL_ERROR};
+use alloy::consensus::Transaction as _;
 use alloy::eips::BlockNumberOrTag;
 use alloy::network::Ethereum;
-use alloy::primitives::TxHash;
+use alloy::primitives::{Address, Bytes, TxHash, B256, U256};
 use alloy::providers::{Provider, ProviderBuilder, WsConnect};
+use alloy::rpc::types::{Filter, TransactionRequest};
+use shared::redact::redact_url;
 use shared::types::block::Block;
+use shared::types::chain_config::{join_args, ChainConfig, ChainStatus, Runtime};
+use shared::types::dev_only_private_key::DevOnlyPrivateKey;
+use shared::types::log::Log;
+use shared::types::log_filter::LogFilter;
+use shared::types::log_line::LogStream;
+use shared::types::mine_until_result::MineUntilResult;
+use shared::types::recording::RecordedTransaction;
+use shared::types::replay_result::ReplayResult;
+use shared::types::resource_usage::ResourceUsage;
 use shared::types::transaction::Transaction;
+use std::collections::HashMap;
+use std::future::IntoFuture;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
 use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
 use std::{process::Stdio, sync::Arc, time::Duration};
 use tokio::net::TcpStream;
 use tokio::{
     io::{AsyncBufReadExt, BufReader},
     process::{Child, Command},
-    sync::broadcast,
+    sync::{broadcast, watch, Semaphore},
     task::JoinHandle,
 };
 use tokio_stream::StreamExt;
 
+/// How many block times may pass without a new block before the chain is
+/// considered stalled, overridable with `LOCALCHAIN_STALL_MULTIPLIER`
+const DEFAULT_STALL_MULTIPLIER: u64 = 5;
+
+fn stall_multiplier() -> u64 {
+    std::env::var("LOCALCHAIN_STALL_MULTIPLIER")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|m| *m > 0)
+        .unwrap_or(DEFAULT_STALL_MULTIPLIER)
+}
+
+/// Readiness poll defaults: 100 × 50ms for a fresh chain, overridable with
+/// `LOCALCHAIN_READY_ATTEMPTS` and `LOCALCHAIN_READY_INTERVAL_MS`
+const DEFAULT_READY_ATTEMPTS: u32 = 100;
+const DEFAULT_READY_INTERVAL_MS: u64 = 50;
+/// Forks have to fetch state from the upstream RPC before anvil binds its
+/// port, so they get this many times the attempts
+const FORK_READY_FACTOR: u32 = 6;
+
+/// How many times to poll the anvil port, and how long to wait between polls
+fn ready_poll(forked: bool) -> (u32, Duration) {
+    let attempts = std::env::var("LOCALCHAIN_READY_ATTEMPTS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|a| *a > 0)
+        .unwrap_or(DEFAULT_READY_ATTEMPTS);
+    let interval = std::env::var("LOCALCHAIN_READY_INTERVAL_MS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|i| *i > 0)
+        .unwrap_or(DEFAULT_READY_INTERVAL_MS);
+    let attempts = if forked {
+        attempts.saturating_mul(FORK_READY_FACTOR)
+    } else {
+        attempts
+    };
+    (attempts, Duration::from_millis(interval))
+}
+
+/// How long the fork endpoint gets to answer `eth_chainId` before a start
+/// gives up without spawning anvil
+const FORK_PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// WebSocket handshakes tried once the port accepts connections, with the
+/// delay between attempts doubling from `WS_CONNECT_BACKOFF`
+const WS_CONNECT_ATTEMPTS: u32 = 5;
+const WS_CONNECT_BACKOFF: Duration = Duration::from_millis(200);
+/// Cap on the delay between reconnects after the connection drops
+const WS_RECONNECT_MAX_BACKOFF: Duration = Duration::from_secs(5);
+
+/// Set while anvil is up, the block subscription swaps in a new provider
+/// when it reconnects
+pub type SharedClient = Arc<std::sync::RwLock<Option<AnvilClient>>>;
+
+/// What calls to a running anvil need, cheap to clone so callers can talk
+/// to anvil without holding the process lock
+#[derive(Clone)]
+pub struct AnvilClient {
+    provider_ws: Arc<dyn Provider<Ethereum>>,
+    /// See `AnvilProcess::fork_block`
+    fork_block: Option<u64>,
+    logs: Arc<LogBuffer>,
+}
+
+/// Picks the prefunded accounts out of anvil's startup banner, which lists
+/// `(i) <address> (<balance>)` under "Available Accounts" and `(i) <key>`
+/// under "Private Keys"
+#[derive(Default)]
+struct BannerParser {
+    section: Option<&'static str>,
+    addresses: Vec<String>,
+}
+
+impl BannerParser {
+    fn feed(&mut self, line: &str) -> Option<DevOnlyPrivateKey> {
+        let line = line.trim();
+        match line {
+            "Available Accounts" => self.section = Some("accounts"),
+            "Private Keys" => self.section = Some("keys"),
+            _ if line.is_empty() || line.starts_with('=') => {}
+            _ if line.starts_with('(') => {
+                let mut parts = line.split_whitespace();
+                let index: u64 = parts.next()?.trim_matches(['(', ')']).parse().ok()?;
+                let value = parts.next()?.to_string();
+                match self.section? {
+                    "accounts" => self.addresses.push(value),
+                    _ => {
+                        return Some(DevOnlyPrivateKey {
+                            index,
+                            address: self.addresses.get(index as usize)?.clone(),
+                            private_key: value,
+                        })
+                    }
+                }
+            }
+            _ => self.section = None,
+        }
+        None
+    }
+}
+
+/// Most blocks one range request may return, and how many of them are
+/// fetched from anvil at once
+pub const MAX_BLOCK_RANGE: u64 = 100;
+const BLOCK_RANGE_CONCURRENCY: usize = 8;
+
+/// Rejects ranges `AnvilClient::get_blocks_range` won't serve, reversed
+/// ones or more than `MAX_BLOCK_RANGE` blocks
+fn check_block_range(from: u64, to: u64) -> Result<(), String> {
+    if to < from {
+        return Err("`to` must not be below `from`".into());
+    }
+    if to - from >= MAX_BLOCK_RANGE {
+        return Err(format!("at most {} blocks per request", MAX_BLOCK_RANGE));
+    }
+    Ok(())
+}
+
+/// Transactions kept while recording, later ones are dropped
+const RECORDING_CAPACITY: usize = 10_000;
+
+/// Upper bound on a single provider call, overridable with
+/// `LOCALCHAIN_RPC_TIMEOUT_MS`
+const DEFAULT_RPC_TIMEOUT_MS: u64 = 10_000;
+
+pub fn rpc_timeout() -> Duration {
+    let ms = std::env::var("LOCALCHAIN_RPC_TIMEOUT_MS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|ms| *ms > 0)
+        .unwrap_or(DEFAULT_RPC_TIMEOUT_MS);
+    Duration::from_millis(ms)
+}
+
+/// How long a whole start may take before the chain is stopped and flagged
+/// `Error`, overridable with `LOCALCHAIN_START_TIMEOUT_SECS`
+const DEFAULT_START_TIMEOUT_SECS: u64 = 120;
+
+pub fn start_timeout() -> Duration {
+    let secs = std::env::var("LOCALCHAIN_START_TIMEOUT_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|secs| *secs > 0)
+        .unwrap_or(DEFAULT_START_TIMEOUT_SECS);
+    Duration::from_secs(secs)
+}
+
+/// Awaits a provider call, failing if anvil doesn't answer within
+/// `rpc_timeout()` so a hung chain can't hold HTTP requests open forever
+async fn rpc<F: IntoFuture>(call: F) -> Result<F::Output, String> {
+    let timeout = rpc_timeout();
+    tokio::time::timeout(timeout, call)
+        .await
+        .map_err(|_| format!("chain unresponsive: no RPC response within {:?}", timeout))
+}
+
+/// HTTP provider for `addr`, if anvil answers `eth_chainId` over it
+async fn http_provider(addr: SocketAddr) -> Option<Arc<dyn Provider<Ethereum>>> {
+    let url = format!("http://{}", addr).parse().ok()?;
+    let provider = ProviderBuilder::new().connect_http(url);
+    rpc(provider.get_chain_id()).await.ok()?.ok()?;
+    Some(Arc::new(provider))
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// What `mine_until` mines towards
+#[derive(Debug, Clone, Copy)]
+pub enum MineCondition {
+    /// The head is at least this block
+    Block(u64),
+    /// The transaction has a receipt, i.e. was included
+    Receipt(TxHash),
+}
+
+impl std::fmt::Display for MineCondition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MineCondition::Block(number) => write!(f, "block {}", number),
+            MineCondition::Receipt(hash) => write!(f, "transaction {} was included", hash),
+        }
+    }
+}
+
+/// Kept current by the block subscription, read by the status stream
+#[derive(Default)]
+pub struct LiveStats {
+    /// Latest block number, 0 until the first block arrives
+    pub head: AtomicU64,
+    pub transactions: AtomicU64,
+    /// Cleared while the subscription's WebSocket is down and reconnecting
+    pub rpc_connected: AtomicBool,
+    /// Set by `set_mining_paused`, cleared on start
+    pub mining_paused: AtomicBool,
+    /// Cleared when anvil only answers over HTTP, there is no block
+    /// subscription then
+    pub streaming_available: AtomicBool,
+}
+
 pub struct AnvilProcess {
     pub name: String,
     pub chain_id: u64,
     pub port: u16,
+    pub host: IpAddr,
     pub block_time: u64,
+    pub heartbeat_secs: Option<u64>,
+    pub premine_blocks: Option<u64>,
     pub fork_url: Option<String>,
+    pub fork_block_number: Option<u64>,
+    pub pin_fork_block: bool,
+    /// Upstream head resolved on the first pinned start, 0 until then
+    pub pinned_fork_block: Arc<AtomicU64>,
+    pub tracing: bool,
+    pub record_transactions: bool,
+    pub env: HashMap<String, String>,
+    pub extra_args: Vec<String>,
+    pub genesis_timestamp: Option<u64>,
+    pub gas_price: Option<u64>,
+    pub block_gas_limit: Option<u64>,
+    pub runtime: Runtime,
+    /// Detected at server startup, requested flags are checked against it
+    /// when known
+    pub anvil_version: Option<AnvilVersion>,
 
     child: Option<Child>,
     pub log_handles: Vec<JoinHandle<()>>,
-    pub log_tx: Arc<broadcast::Sender<String>>,
+    pub logs: Arc<LogBuffer>,
     pub block_tx: Arc<broadcast::Sender<Block>>,
     pub block_handle: Option<JoinHandle<()>>,
-    provider_ws: Option<Arc<dyn Provider<Ethereum>>>,
+    pub status_tx: Arc<watch::Sender<ChainStatus>>,
+    /// Unix time at which the block subscription last saw a block
+    last_block_at: Arc<AtomicU64>,
+    watchdog_handle: Option<JoinHandle<()>>,
+    heartbeat_handle: Option<JoinHandle<()>>,
+    /// Upstream block the running fork was taken at, blocks up to it are
+    /// not ours
+    fork_block: Option<u64>,
+    /// Accounts from the startup banner of the current run
+    dev_keys: Arc<std::sync::Mutex<Vec<DevOnlyPrivateKey>>>,
+    pub stats: Arc<LiveStats>,
+    /// Result of `eth_chainId` after the last start, 0 until known
+    pub actual_chain_id: Arc<AtomicU64>,
+    /// Unix time the current run came up, 0 while stopped
+    pub started_at: Arc<AtomicU64>,
+    /// `anvil_dumpState` output loaded into the next run, then dropped
+    pub initial_state: Option<String>,
+    /// Mined transactions while `record_transactions` is set, reset by
+    /// starts from genesis
+    pub recording: Arc<std::sync::Mutex<Vec<RecordedTransaction>>>,
+    /// Block 0 and its transactions once asked for, dropped on start
+    genesis: Option<(Block, Vec<Transaction>)>,
+    pub client: SharedClient,
 }
 
 impl AnvilProcess {
     pub fn new(
-        name: String,
-        chain_id: u64,
-        port: u16,
-        block_time: u64,
-        log_tx: Arc<broadcast::Sender<String>>,
+        config: &ChainConfig,
+        logs: Arc<LogBuffer>,
         block_tx: Arc<broadcast::Sender<Block>>,
-        fork_url: Option<String>,
+        status_tx: Arc<watch::Sender<ChainStatus>>,
     ) -> Self {
         Self {
-            name,
-            chain_id,
-            port,
-            block_time,
+            name: config.name.clone(),
+            chain_id: config.id,
+            port: config.port,
+            host: config
+                .host()
+                .parse()
+                .unwrap_or(IpAddr::V4(Ipv4Addr::LOCALHOST)),
+            block_time: config.block_time,
+            heartbeat_secs: config.heartbeat_secs,
+            premine_blocks: config.premine_blocks,
             child: None,
             log_handles: Vec::new(),
-            log_tx,
+            logs,
             block_tx,
             block_handle: None,
-            provider_ws: None,
-            fork_url,
+            status_tx,
+            last_block_at: Arc::new(AtomicU64::new(0)),
+            watchdog_handle: None,
+            heartbeat_handle: None,
+            fork_block: None,
+            dev_keys: Default::default(),
+            stats: Default::default(),
+            actual_chain_id: Arc::new(AtomicU64::new(0)),
+            started_at: Arc::new(AtomicU64::new(0)),
+            initial_state: None,
+            client: Default::default(),
+            fork_url: config.fork_url.clone(),
+            fork_block_number: config.fork_block_number,
+            pin_fork_block: config.pin_fork_block,
+            pinned_fork_block: Arc::new(AtomicU64::new(0)),
+            tracing: config.tracing,
+            record_transactions: config.record_transactions,
+            recording: Arc::new(std::sync::Mutex::new(vec![])),
+            genesis: None,
+            env: config.env.clone(),
+            extra_args: config.extra_args.clone(),
+            genesis_timestamp: config.genesis_timestamp,
+            gas_price: config.gas_price,
+            block_gas_limit: config.block_gas_limit,
+            runtime: config.runtime(),
+            anvil_version: None,
         }
     }
 
@@ -59,126 +352,652 @@ impl AnvilProcess {
         if self.child.is_some() {
             self.stop().await?;
         }
+        // published on `host` by docker, the container's own interface is
+        // what anvil sees
+        let listen = match self.runtime {
+            Runtime::Native => self.host,
+            Runtime::Docker => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+        };
         let mut cmd = Command::new("anvil");
         cmd.arg("--port")
             .arg(self.port.to_string())
+            .arg("--host")
+            .arg(listen.to_string())
             .arg("--chain-id")
-            .arg(self.chain_id.to_string())
-            .arg("--block-time")
-            .arg(self.block_time.to_string());
+            .arg(self.chain_id.to_string());
+        // without --block-time anvil mines one block per transaction
+        if self.block_time > 0 {
+            cmd.arg("--block-time").arg(self.block_time.to_string());
+        }
 
         if let Some(fork_url) = &self.fork_url {
+            self.probe_fork(fork_url).await?;
             cmd.arg("--fork-url").arg(fork_url);
+            if let Some(number) = self.fork_block_number().await? {
+                cmd.arg("--fork-block-number").arg(number.to_string());
+            }
+        }
+        if let Some(timestamp) = self.genesis_timestamp {
+            cmd.arg("--timestamp").arg(timestamp.to_string());
+        }
+        if let Some(gas_price) = self.gas_price {
+            cmd.arg("--gas-price").arg(gas_price.to_string());
+        }
+        if let Some(limit) = self.block_gas_limit {
+            cmd.arg("--gas-limit").arg(limit.to_string());
+        }
+        if self.tracing {
+            cmd.arg("--steps-tracing");
+        }
+        cmd.args(&self.extra_args);
+        cmd.envs(&self.env);
+        // the detected version is the local binary's, not the image's
+        if self.runtime == Runtime::Docker {
+            // a container left running by a crashed server holds the name
+            docker::stop(self.chain_id).await?;
+            cmd = docker::command(&cmd, self.chain_id, self.host, self.port);
+        } else if let Some(version) = self.anvil_version {
+            version.check(cmd.as_std().get_args())?;
         }
 
-        println!(
-            "[{}] Starting Anvil (chainId={}, port={}, blockTime={:?})",
-            self.name, self.chain_id, self.port, self.block_time
-        );
-        cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
+        let command_line = {
+            let cmd = cmd.as_std();
+            let mut parts = vec![cmd.get_program().to_string_lossy().into_owned()];
+            parts.extend(cmd.get_args().map(|a| redact_url(&a.to_string_lossy())));
+            join_args(&parts)
+        };
+        println!("[{}] Starting Anvil: {}", self.name, command_line);
+        self.logs
+            .send(LogStream::Manager, format!("exec: {}", command_line));
+
+        // a start that fails or times out half way must not leave anvil behind
+        cmd.stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
 
         let mut child = cmd.spawn().map_err(|e| e.to_string())?;
-        let log_tx = self.log_tx.clone();
+        // anvil echoes the fork endpoint in its banner
+        let secret = self
+            .fork_url
+            .clone()
+            .map(|url| (redact_url(&url), url))
+            .filter(|(masked, url)| masked != url);
+        let mask = move |line: String| match &secret {
+            Some((masked, url)) => line.replace(url, masked),
+            None => line,
+        };
+        let logs = self.logs.clone();
 
+        self.dev_keys.lock().unwrap().clear();
+        let dev_keys = self.dev_keys.clone();
         if let Some(stdout) = child.stdout.take() {
             let mut reader = BufReader::new(stdout).lines();
+            let mask = mask.clone();
             let handle = tokio::spawn(async move {
+                let mut banner = BannerParser::default();
                 while let Ok(Some(line)) = reader.next_line().await {
-                    let _ = log_tx.send(format!("[stdout] {}", line));
+                    if let Some(key) = banner.feed(&line) {
+                        dev_keys.lock().unwrap().push(key);
+                    }
+                    logs.send(LogStream::Stdout, mask(line));
                 }
             });
             self.log_handles.push(handle);
         }
 
-        let log_tx = self.log_tx.clone();
+        let logs = self.logs.clone();
         if let Some(stderr) = child.stderr.take() {
             let mut reader = BufReader::new(stderr).lines();
             let handle = tokio::spawn(async move {
                 while let Ok(Some(line)) = reader.next_line().await {
-                    let _ = log_tx.send(format!("[stderr] {}", line));
+                    logs.send(LogStream::Stderr, mask(line));
                 }
             });
             self.log_handles.push(handle);
         }
 
+        if let Some(fork_url) = &self.fork_url {
+            self.logs.send(
+                LogStream::Manager,
+                format!(
+                    "connecting to fork {}, this can take a while...",
+                    redact_url(fork_url)
+                ),
+            );
+        }
+        self.logs
+            .send(LogStream::Manager, "waiting for anvil RPC...".into());
         let provider_ws = {
-            let port = self.port;
+            let addr = self.rpc_addr();
+            let (attempts, interval) = ready_poll(self.fork_url.is_some());
             let mut attempt = 0;
-            let max_attempts = 50;
             loop {
-                match tokio::time::timeout(
-                    Duration::from_millis(100),
-                    TcpStream::connect(format!("127.0.0.1:{}", port)),
-                )
-                .await
-                {
+                match tokio::time::timeout(interval, TcpStream::connect(addr)).await {
                     Ok(Ok(_)) => break,
-                    Ok(Err(_)) => {
+                    Ok(Err(_)) | Err(_) => {
                         attempt += 1;
-                        if attempt >= max_attempts {
-                            return Err("Failed to connect to websocket".into());
+                        if attempt >= attempts {
+                            let waited = interval * attempts;
+                            return Err(if self.fork_url.is_some() {
+                                format!(
+                                    "Timed out after {:?} waiting for forked anvil to accept connections",
+                                    waited
+                                )
+                            } else {
+                                format!(
+                                    "Timed out after {:?} waiting for anvil to accept connections",
+                                    waited
+                                )
+                            });
                         }
-                        tokio::time::sleep(Duration::from_millis(100)).await;
+                        tokio::time::sleep(interval).await;
                     }
-                    Err(_) => {
-                        attempt += 1;
-                        if attempt >= max_attempts {
-                            return Err("Failed to connect to websocket".into());
-                        }
-                        tokio::time::sleep(Duration::from_millis(100)).await;
+                };
+            }
+            let mut attempt = 1;
+            let mut backoff = WS_CONNECT_BACKOFF;
+            loop {
+                let ws = WsConnect::new(format!("ws://{}", addr));
+                let e = match ProviderBuilder::new().connect_ws(ws).await {
+                    Ok(provider) => {
+                        break (Arc::new(provider) as Arc<dyn Provider<Ethereum>>, true)
                     }
+                    Err(e) => e,
                 };
+                // anvil answering over HTTP means its WebSocket is off, not
+                // that it isn't up yet
+                if let Some(provider) = http_provider(addr).await {
+                    self.logs.send(
+                        LogStream::Manager,
+                        format!(
+                            "WebSocket RPC unavailable ({}), using HTTP; live block streaming is off",
+                            e
+                        ),
+                    );
+                    break (provider, false);
+                }
+                if attempt >= WS_CONNECT_ATTEMPTS {
+                    return Err(format!(
+                        "Failed to connect to anvil RPC after {} attempts: {}",
+                        attempt, e
+                    ));
+                }
+                self.logs.send(
+                    LogStream::Manager,
+                    format!(
+                        "RPC connection attempt {}/{} failed ({}), retrying in {}ms",
+                        attempt,
+                        WS_CONNECT_ATTEMPTS,
+                        e,
+                        backoff.as_millis()
+                    ),
+                );
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+                attempt += 1;
+            }
+        };
+        let (provider_ws, streaming) = provider_ws;
+        self.stats
+            .streaming_available
+            .store(streaming, Ordering::Relaxed);
+        self.logs
+            .send(LogStream::Manager, "connected to anvil RPC".into());
+        // a fork can come up with the upstream network's id instead of ours
+        match rpc(provider_ws.get_chain_id())
+            .await
+            .and_then(|r| r.map_err(|e| e.to_string()))
+        {
+            Ok(actual) => {
+                self.actual_chain_id.store(actual, Ordering::Relaxed);
+                if actual != self.chain_id {
+                    self.logs.send(
+                        LogStream::Manager,
+                        format!(
+                            "warning: chain id mismatch, configured {} but anvil reports {}",
+                            self.chain_id, actual
+                        ),
+                    );
+                }
             }
-            let ws = WsConnect::new(format!("ws://127.0.0.1:{}", port));
-            let provider = ProviderBuilder::new()
-                .connect_ws(ws)
+            Err(e) => self.logs.send(
+                LogStream::Manager,
+                format!("warning: could not read chain id: {}", e),
+            ),
+        }
+        // anvil mines on top of the upstream head it forked from
+        self.fork_block = None;
+        if self.fork_url.is_some() {
+            match rpc(provider_ws.get_block_number())
                 .await
-                .map_err(|e| e.to_string())?;
-            provider
+                .and_then(|r| r.map_err(|e| e.to_string()))
+            {
+                Ok(number) => self.fork_block = Some(number),
+                Err(e) => self.logs.send(
+                    LogStream::Manager,
+                    format!("warning: could not read fork block: {}", e),
+                ),
+            }
+        }
+        let client = AnvilClient {
+            provider_ws,
+            fork_block: self.fork_block,
+            logs: self.logs.clone(),
         };
-        self.provider_ws = Some(Arc::new(provider_ws));
+        self.genesis = None;
+        if self.initial_state.is_none() {
+            self.recording.lock().unwrap().clear();
+        }
+        if let Some(state) = &self.initial_state {
+            client.load_state(state).await?;
+            self.initial_state = None;
+            self.logs
+                .send(LogStream::Manager, "loaded state from source chain".into());
+        } else if let Some(blocks) = self.premine_blocks.filter(|n| *n > 0) {
+            // a loaded state already has its height, only fresh chains premine
+            client.mine(blocks).await?;
+            self.logs
+                .send(LogStream::Manager, format!("pre-mined {} blocks", blocks));
+        }
+        // only now, so requests never see a chain whose state is still loading
+        *self.client.write().unwrap() = Some(client);
 
         let block_tx = self.block_tx.clone();
-        let provider_ws = self.provider_ws.clone().unwrap();
-        let block_handle = tokio::spawn(async move {
-            if let Err(e) = async {
-                let mut stream = provider_ws.subscribe_blocks().await?.into_stream();
-
-                while let Some(header) = stream.next().await {
-                    let block_num = BlockNumberOrTag::Number(header.number);
-                    if let Ok(Some(block)) = provider_ws.get_block_by_number(block_num).await {
-                        let _ = block_tx.send(Block {
-                            beneficiary: header.beneficiary.to_string(),
-                            gas_limit: header.gas_limit,
-                            gas_used: header.gas_used,
-                            number: header.number,
-                            hash: header.hash.to_string(),
-                            time: header.timestamp,
-                            nonce: header.nonce.to_string(),
-                            transactions: block.transactions.len() as u64,
-                        });
-                    } else {
-                        println!("Error getting Block {}", header.number);
+        let mut provider_ws = self.provider()?;
+        let slot = self.client.clone();
+        let addr = self.rpc_addr();
+        let logs = self.logs.clone();
+        let last_block_at = self.last_block_at.clone();
+        let fork_block = self.fork_block;
+        let stats = self.stats.clone();
+        let recording = self.record_transactions.then(|| self.recording.clone());
+        stats.head.store(0, Ordering::Relaxed);
+        stats.transactions.store(0, Ordering::Relaxed);
+        stats.rpc_connected.store(true, Ordering::Relaxed);
+        stats.mining_paused.store(false, Ordering::Relaxed);
+        last_block_at.store(unix_now(), Ordering::Relaxed);
+        // an HTTP provider can't subscribe, the chain runs without live blocks
+        self.block_handle = streaming.then(|| {
+            tokio::spawn(async move {
+                // runs until stop aborts it, reconnecting whenever the socket drops
+                loop {
+                    let reason = match async {
+                        let mut stream = provider_ws.subscribe_blocks().await?.into_stream();
+
+                        while let Some(header) = stream.next().await {
+                            last_block_at.store(unix_now(), Ordering::Relaxed);
+                            let block_num = BlockNumberOrTag::Number(header.number);
+                            if let Ok(Ok(Some(block))) =
+                                rpc(provider_ws.get_block_by_number(block_num)).await
+                            {
+                                stats.head.store(header.number, Ordering::Relaxed);
+                                stats
+                                    .transactions
+                                    .fetch_add(block.transactions.len() as u64, Ordering::Relaxed);
+                                let _ = block_tx.send(Block {
+                                    beneficiary: header.beneficiary.to_string(),
+                                    gas_limit: header.gas_limit,
+                                    gas_used: header.gas_used,
+                                    number: header.number,
+                                    hash: header.hash.to_string(),
+                                    time: header.timestamp,
+                                    nonce: header.nonce.to_string(),
+                                    transactions: block.transactions.len() as u64,
+                                    is_local: fork_block.is_none_or(|f| header.number > f),
+                                });
+                                if let Some(recording) = &recording {
+                                    for hash in block.transactions.hashes() {
+                                        match rpc(provider_ws.get_raw_transaction_by_hash(hash))
+                                            .await
+                                        {
+                                            Ok(Ok(Some(raw))) => {
+                                                let mut recording = recording.lock().unwrap();
+                                                if recording.len() < RECORDING_CAPACITY {
+                                                    recording.push(RecordedTransaction {
+                                                        block_number: header.number,
+                                                        hash: hash.to_string(),
+                                                        raw: raw.to_string(),
+                                                    });
+                                                }
+                                            }
+                                            _ => logs.send(
+                                                LogStream::Manager,
+                                                format!("could not record transaction {}", hash),
+                                            ),
+                                        }
+                                    }
+                                }
+                            } else {
+                                println!("Error getting Block {}", header.number);
+                            }
+                        }
+                        Ok::<(), anyhow::Error>(())
                     }
+                    .await
+                    {
+                        Ok(()) => "subscription ended".to_string(),
+                        Err(e) => e.to_string(),
+                    };
+                    stats.rpc_connected.store(false, Ordering::Relaxed);
+                    logs.send(
+                        LogStream::Manager,
+                        format!("RPC disconnected ({}), reconnecting", reason),
+                    );
+                    let mut backoff = WS_CONNECT_BACKOFF;
+                    provider_ws = loop {
+                        tokio::time::sleep(backoff).await;
+                        let ws = WsConnect::new(format!("ws://{}", addr));
+                        match ProviderBuilder::new().connect_ws(ws).await {
+                            Ok(provider) => break Arc::new(provider),
+                            Err(_) => backoff = (backoff * 2).min(WS_RECONNECT_MAX_BACKOFF),
+                        }
+                    };
+                    *slot.write().unwrap() = Some(AnvilClient {
+                        provider_ws: provider_ws.clone(),
+                        fork_block,
+                        logs: logs.clone(),
+                    });
+                    stats.rpc_connected.store(true, Ordering::Relaxed);
+                    logs.send(LogStream::Manager, "RPC reconnected".into());
                 }
-                Ok::<(), anyhow::Error>(())
-            }
-            .await
-            {
-                println!("Block stream error: {:?}", e);
-            }
+            })
         });
-        self.block_handle = Some(block_handle);
+        self.watchdog_handle = self.spawn_watchdog();
+        self.heartbeat_handle = self.spawn_heartbeat()?;
 
         self.child = Some(child);
+        self.started_at.store(unix_now(), Ordering::Relaxed);
+        self.logs.send(LogStream::Manager, "ready".into());
+        Ok(())
+    }
+
+    /// Flags the chain as `Error` when interval mining stops producing blocks,
+    /// and back to `Running` once they resume
+    fn spawn_watchdog(&self) -> Option<JoinHandle<()>> {
+        // without the block subscription nothing would report new blocks
+        if self.block_time == 0 || !self.stats.streaming_available.load(Ordering::Relaxed) {
+            return None;
+        }
+        let block_time = self.block_time;
+        let stall_after = block_time * stall_multiplier();
+        let last_block_at = self.last_block_at.clone();
+        let logs = self.logs.clone();
+        let status_tx = self.status_tx.clone();
+        Some(tokio::spawn(async move {
+            let mut stalled = false;
+            let mut interval = tokio::time::interval(Duration::from_secs(block_time));
+            loop {
+                interval.tick().await;
+                let idle = unix_now().saturating_sub(last_block_at.load(Ordering::Relaxed));
+                if idle >= stall_after && !stalled {
+                    stalled = true;
+                    logs.send(LogStream::Manager, format!("no blocks for {}s", idle));
+                    status_tx.send_replace(ChainStatus::Error);
+                } else if idle < stall_after && stalled {
+                    stalled = false;
+                    logs.send(LogStream::Manager, "blocks resumed".into());
+                    status_tx.send_replace(ChainStatus::Running);
+                }
+            }
+        }))
+    }
+
+    /// Mines an empty block every `heartbeat_secs` on chains that only mine
+    /// on transactions
+    fn spawn_heartbeat(&self) -> Result<Option<JoinHandle<()>>, String> {
+        let Some(secs) = self.heartbeat_secs.filter(|_| self.block_time == 0) else {
+            return Ok(None);
+        };
+        let provider_ws = self.provider()?;
+        let logs = self.logs.clone();
+        Ok(Some(tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(secs));
+            // the first tick completes immediately
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                let result = match serde_json::value::to_raw_value(&()) {
+                    Ok(params) => rpc(provider_ws.raw_request_dyn("anvil_mine".into(), &params))
+                        .await
+                        .and_then(|r| r.map_err(|e| e.to_string())),
+                    Err(e) => Err(e.to_string()),
+                };
+                if let Err(e) = result {
+                    logs.send(LogStream::Manager, format!("heartbeat failed: {}", e));
+                }
+            }
+        })))
+    }
+
+    /// Switches a running chain to interval mining every `secs`, or back to
+    /// mining per transaction for `0`, and respawns the watchdog and
+    /// heartbeat for the new mode
+    pub async fn set_block_time(&mut self, secs: u64) -> Result<(), String> {
+        let provider_ws = self.provider()?;
+        let (method, params) = if secs == 0 {
+            ("evm_setAutomine", serde_json::value::to_raw_value(&(true,)))
+        } else {
+            (
+                "evm_setIntervalMining",
+                serde_json::value::to_raw_value(&(secs,)),
+            )
+        };
+        let params = params.map_err(|e| e.to_string())?;
+        rpc(provider_ws.raw_request_dyn(method.into(), &params))
+            .await?
+            .map_err(|e| format!("Failed to set block time: {}", e))?;
+        self.block_time = secs;
+        self.stats.mining_paused.store(false, Ordering::Relaxed);
+        self.respawn_monitors()?;
+        self.logs.send(
+            LogStream::Manager,
+            match secs {
+                0 => "mining per transaction".into(),
+                secs => format!("block time set to {}s", secs),
+            },
+        );
         Ok(())
     }
 
+    /// Changes the gas limit of the blocks a running chain mines from now on
+    pub async fn set_block_gas_limit(&mut self, limit: u64) -> Result<(), String> {
+        let provider_ws = self.provider()?;
+        let params = serde_json::value::to_raw_value(&(format!("{:#x}", limit),))
+            .map_err(|e| e.to_string())?;
+        rpc(provider_ws.raw_request_dyn("evm_setBlockGasLimit".into(), &params))
+            .await?
+            .map_err(|e| format!("Failed to set block gas limit: {}", e))?;
+        self.block_gas_limit = Some(limit);
+        self.logs.send(
+            LogStream::Manager,
+            format!("block gas limit set to {}", limit),
+        );
+        Ok(())
+    }
+
+    /// Turns all mining off, or back on under the configured block time.
+    /// While paused blocks only come from `mine`.
+    pub async fn set_mining_paused(&mut self, paused: bool) -> Result<(), String> {
+        let provider_ws = self.provider()?;
+        let calls = match (paused, self.block_time) {
+            (true, _) => vec![
+                ("evm_setAutomine", serde_json::json!([false])),
+                ("evm_setIntervalMining", serde_json::json!([0])),
+            ],
+            (false, 0) => vec![("evm_setAutomine", serde_json::json!([true]))],
+            (false, secs) => vec![("evm_setIntervalMining", serde_json::json!([secs]))],
+        };
+        for (method, params) in calls {
+            let params = serde_json::value::to_raw_value(&params).map_err(|e| e.to_string())?;
+            rpc(provider_ws.raw_request_dyn(method.into(), &params))
+                .await?
+                .map_err(|e| format!("Failed to set mining mode: {}", e))?;
+        }
+        self.stats.mining_paused.store(paused, Ordering::Relaxed);
+        self.respawn_monitors()?;
+        self.logs.send(
+            LogStream::Manager,
+            if paused {
+                "mining paused"
+            } else {
+                "mining resumed"
+            }
+            .into(),
+        );
+        Ok(())
+    }
+
+    /// Restarts the watchdog and heartbeat after the mining mode changed,
+    /// neither runs while mining is paused
+    fn respawn_monitors(&mut self) -> Result<(), String> {
+        for handle in self
+            .watchdog_handle
+            .take()
+            .into_iter()
+            .chain(self.heartbeat_handle.take())
+        {
+            handle.abort();
+        }
+        if self.stats.mining_paused.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        // time spent in the old mode shouldn't count as a stall in the new one
+        self.last_block_at.store(unix_now(), Ordering::Relaxed);
+        self.watchdog_handle = self.spawn_watchdog();
+        self.heartbeat_handle = self.spawn_heartbeat()?;
+        Ok(())
+    }
+
+    /// Accounts and private keys anvil printed when it started
+    pub fn dev_private_keys(&self) -> Result<Vec<DevOnlyPrivateKey>, String> {
+        if self.child.is_none() {
+            return Err("chain is not running".into());
+        }
+        Ok(self.dev_keys.lock().unwrap().clone())
+    }
+
+    /// False when anvil reported a different chain id than configured
+    pub fn chain_id_matches(&self) -> bool {
+        let actual = self.actual_chain_id.load(Ordering::Relaxed);
+        actual == 0 || actual == self.chain_id
+    }
+
+    /// Where to reach anvil, a wildcard bind is reached over loopback
+    fn rpc_addr(&self) -> SocketAddr {
+        let ip = match self.host {
+            IpAddr::V4(ip) if ip.is_unspecified() => IpAddr::V4(Ipv4Addr::LOCALHOST),
+            IpAddr::V6(ip) if ip.is_unspecified() => IpAddr::V6(Ipv6Addr::LOCALHOST),
+            ip => ip,
+        };
+        SocketAddr::new(ip, self.port)
+    }
+
+    /// Asks the fork endpoint for its chain id, so a mistyped or dead URL
+    /// fails the start right away instead of after anvil's own retries
+    async fn probe_fork(&self, fork_url: &str) -> Result<(), String> {
+        // transport errors quote the URL, key included
+        let mask = |e: String| e.replace(fork_url, &redact_url(fork_url));
+        let probe = async {
+            let upstream = ProviderBuilder::new()
+                .connect(fork_url)
+                .await
+                .map_err(|e| mask(e.to_string()))?;
+            upstream
+                .get_chain_id()
+                .await
+                .map_err(|e| mask(e.to_string()))
+        };
+        let chain_id = tokio::time::timeout(FORK_PROBE_TIMEOUT, probe)
+            .await
+            .unwrap_or_else(|_| Err(format!("no answer within {:?}", FORK_PROBE_TIMEOUT)))
+            .map_err(|e| format!("fork endpoint unreachable or returned error: {}", e))?;
+        self.logs.send(
+            LogStream::Manager,
+            format!("fork endpoint is chain {}", chain_id),
+        );
+        Ok(())
+    }
+
+    /// The configured fork block, else with `pin_fork_block` the upstream
+    /// head, which is asked for once and reused by later starts
+    async fn fork_block_number(&self) -> Result<Option<u64>, String> {
+        if self.fork_block_number.is_some() || !self.pin_fork_block {
+            return Ok(self.fork_block_number);
+        }
+        let pinned = self.pinned_fork_block.load(Ordering::Relaxed);
+        if pinned != 0 {
+            return Ok(Some(pinned));
+        }
+        let Some(fork_url) = &self.fork_url else {
+            return Ok(None);
+        };
+        let upstream = ProviderBuilder::new()
+            .connect(fork_url)
+            .await
+            .map_err(|e| format!("Failed to connect to fork: {}", redact_url(&e.to_string())))?;
+        let head = upstream
+            .get_block_number()
+            .await
+            .map_err(|e| format!("Failed to read fork head: {}", redact_url(&e.to_string())))?;
+        self.pinned_fork_block.store(head, Ordering::Relaxed);
+        self.logs
+            .send(LogStream::Manager, format!("pinned fork to block {}", head));
+        Ok(Some(head))
+    }
+
+    /// Client for the running chain, dropped on stop so nothing talks to a
+    /// dead socket
+    pub fn client(&self) -> Result<AnvilClient, String> {
+        self.client
+            .read()
+            .unwrap()
+            .clone()
+            .ok_or_else(|| "chain not started".into())
+    }
+
+    fn provider(&self) -> Result<Arc<dyn Provider<Ethereum>>, String> {
+        self.client().map(|client| client.provider_ws)
+    }
+
     pub async fn stop(&mut self) -> Result<(), String> {
+        self.actual_chain_id.store(0, Ordering::Relaxed);
+        self.started_at.store(0, Ordering::Relaxed);
+        let handles: Vec<JoinHandle<()>> = self
+            .block_handle
+            .take()
+            .into_iter()
+            .chain(self.watchdog_handle.take())
+            .chain(self.heartbeat_handle.take())
+            .chain(self.log_handles.drain(..))
+            .collect();
+        let aborted = handles.len();
+        for handle in &handles {
+            handle.abort();
+        }
+        // abort only takes effect at the task's next await, wait for the
+        // readers to exit so no output lands after the caller's last line
+        for handle in handles {
+            let _ = handle.await;
+        }
+        // after the subscription is gone so it can't put a provider back
+        *self.client.write().unwrap() = None;
+        if aborted > 0 {
+            self.logs.send(
+                LogStream::Manager,
+                format!("stopped {} background tasks", aborted),
+            );
+        }
+        // killing `docker run` would leave the container running, and a start
+        // that failed after the spawn dropped its child without stopping it
+        if self.runtime == Runtime::Docker {
+            if let Err(e) = docker::stop(self.chain_id).await {
+                self.logs.send(LogStream::Manager, e);
+            }
+        }
         if let Some(mut child) = self.child.take() {
             match child.kill().await {
                 Ok(_) => {
-                    let _ = child.wait();
+                    let _ = child.wait().await;
                 }
                 Err(e) => {
                     return Err(e.to_string());
@@ -189,17 +1008,84 @@ impl AnvilProcess {
         Ok(())
     }
 
+    /// Pid of the running anvil child, `None` while stopped
+    pub fn pid(&self) -> Option<u32> {
+        self.child.as_ref().and_then(|child| child.id())
+    }
+
+    #[cfg(target_os = "linux")]
+    pub async fn resource_usage(&self) -> Result<ResourceUsage, String> {
+        // USER_HZ is fixed at 100 for the /proc interface on every mainstream arch
+        const CLOCK_TICKS_PER_SEC: u64 = 100;
+
+        let pid = self.pid().ok_or("chain is not running")?;
+        if self.runtime == Runtime::Docker {
+            return Err("resource usage isn't available for chains running in Docker".into());
+        }
+        let stat = tokio::fs::read_to_string(format!("/proc/{}/stat", pid))
+            .await
+            .map_err(|e| e.to_string())?;
+        // the command name is wrapped in parens and may contain spaces, so
+        // count fields from the closing paren; utime and stime are fields 14, 15
+        let fields: Vec<&str> = stat
+            .rsplit_once(')')
+            .map(|(_, rest)| rest.split_whitespace().collect())
+            .unwrap_or_default();
+        let field = |n: usize| -> Result<u64, String> {
+            fields
+                .get(n - 3)
+                .and_then(|v| v.parse().ok())
+                .ok_or_else(|| format!("unexpected /proc/{}/stat format", pid))
+        };
+        let cpu_ticks = field(14)? + field(15)?;
+
+        let status = tokio::fs::read_to_string(format!("/proc/{}/status", pid))
+            .await
+            .map_err(|e| e.to_string())?;
+        let rss_kb = status
+            .lines()
+            .find_map(|line| line.strip_prefix("VmRSS:"))
+            .and_then(|v| v.trim().trim_end_matches("kB").trim().parse::<u64>().ok())
+            .ok_or_else(|| format!("unexpected /proc/{}/status format", pid))?;
+
+        Ok(ResourceUsage {
+            pid,
+            rss_bytes: rss_kb * 1024,
+            cpu_time_ms: cpu_ticks * 1000 / CLOCK_TICKS_PER_SEC,
+        })
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub async fn resource_usage(&self) -> Result<ResourceUsage, String> {
+        Err("resource usage is only available on Linux".into())
+    }
+
+    /// Block 0, fetched from anvil once per start since it can't change
+    /// while the chain runs
+    pub async fn genesis(&mut self) -> Result<(Block, Vec<Transaction>), String> {
+        if let Some(genesis) = &self.genesis {
+            return Ok(genesis.clone());
+        }
+        let genesis = self.client()?.get_block_with_transactions(0).await?;
+        self.genesis = Some(genesis.clone());
+        Ok(genesis)
+    }
+}
+
+impl AnvilClient {
+    fn is_local(&self, block_number: u64) -> bool {
+        self.fork_block.is_none_or(|f| block_number > f)
+    }
+
     pub async fn get_block_with_transactions(
         &self,
         block_number: u64,
     ) -> Result<(Block, Vec<Transaction>), String> {
-        let provider_ws = self.provider_ws.clone().unwrap();
+        let provider_ws = &self.provider_ws;
 
         let block_num = BlockNumberOrTag::Number(block_number);
-        let block = provider_ws
-            .get_block_by_number(block_num)
-            .full()
-            .await
+        let block = rpc(provider_ws.get_block_by_number(block_num).full())
+            .await?
             .map_err(|e| format!("Failed to get block: {}", e))?
             .ok_or_else(|| format!("Block {} not found", block_number))?;
 
@@ -209,9 +1095,13 @@ impl AnvilProcess {
             .txns()
             .map(|tx: &alloy::rpc::types::Transaction| Transaction {
                 hash: tx.inner.hash().to_string(),
-                block_number: tx.block_number.unwrap_or(block_number_value),
-                index: tx.transaction_index.unwrap_or_default(),
+                block_number: Some(tx.block_number.unwrap_or(block_number_value)),
+                index: Some(tx.transaction_index.unwrap_or_default()),
                 from: tx.inner.signer().to_string(),
+                to: tx.to().map(|to| to.to_string()),
+                value: tx.value().to_string(),
+                gas: tx.gas_limit(),
+                input: tx.input().to_string(),
             })
             .collect();
 
@@ -225,26 +1115,342 @@ impl AnvilProcess {
                 time: block.header.timestamp,
                 nonce: block.header.nonce.to_string(),
                 transactions: transactions.len() as u64,
+                is_local: self.is_local(block.header.number),
             },
             transactions,
         ))
     }
 
+    /// Blocks `from..=to` without their transaction bodies, skipping numbers
+    /// past the head
+    pub async fn get_blocks_range(&self, from: u64, to: u64) -> Result<Vec<Block>, String> {
+        check_block_range(from, to)?;
+        let provider_ws = &self.provider_ws;
+        let permits = Semaphore::new(BLOCK_RANGE_CONCURRENCY);
+
+        let blocks = futures::future::join_all((from..=to).map(|number| {
+            let provider_ws = provider_ws.clone();
+            let permits = &permits;
+            async move {
+                let _permit = permits.acquire().await.map_err(|e| e.to_string())?;
+                rpc(provider_ws.get_block_by_number(BlockNumberOrTag::Number(number)))
+                    .await?
+                    .map_err(|e| format!("Failed to get block {}: {}", number, e))
+            }
+        }))
+        .await;
+
+        let mut range = vec![];
+        for block in blocks {
+            if let Some(block) = block? {
+                range.push(Block {
+                    beneficiary: block.header.beneficiary.to_string(),
+                    gas_limit: block.header.gas_limit,
+                    gas_used: block.header.gas_used,
+                    number: block.header.number,
+                    hash: block.header.hash.to_string(),
+                    time: block.header.timestamp,
+                    nonce: block.header.nonce.to_string(),
+                    transactions: block.transactions.len() as u64,
+                    is_local: self.is_local(block.header.number),
+                });
+            }
+        }
+        Ok(range)
+    }
+
+    /// Number of the latest block
+    pub async fn block_number(&self) -> Result<u64, String> {
+        let provider_ws = &self.provider_ws;
+        rpc(provider_ws.get_block_number())
+            .await?
+            .map_err(|e| e.to_string())
+    }
+
+    /// Chain state as returned by `anvil_dumpState`, accounts, storage and
+    /// blocks in one hex encoded blob
+    pub async fn dump_state(&self) -> Result<String, String> {
+        let provider_ws = &self.provider_ws;
+        let params = serde_json::value::to_raw_value(&()).map_err(|e| e.to_string())?;
+        let state = rpc(provider_ws.raw_request_dyn("anvil_dumpState".into(), &params))
+            .await?
+            .map_err(|e| e.to_string())?;
+        serde_json::from_str(state.get()).map_err(|e| e.to_string())
+    }
+
+    /// Mines `blocks` blocks right away, whatever the mining mode
+    pub async fn mine(&self, blocks: u64) -> Result<(), String> {
+        let provider_ws = &self.provider_ws;
+        let params = serde_json::value::to_raw_value(&(format!("{:#x}", blocks),))
+            .map_err(|e| e.to_string())?;
+        rpc(provider_ws.raw_request_dyn("anvil_mine".into(), &params))
+            .await?
+            .map_err(|e| format!("Failed to mine blocks: {}", e))?;
+        Ok(())
+    }
+
+    /// Mines until `condition` holds, at most `max_blocks` blocks. Blocks
+    /// anvil mines on its own in the meantime count towards the condition
+    /// but not towards `mined`.
+    pub async fn mine_until(
+        &self,
+        condition: MineCondition,
+        max_blocks: u64,
+    ) -> Result<MineUntilResult, String> {
+        let provider_ws = &self.provider_ws;
+        let mut mined = 0;
+        let reached = match condition {
+            MineCondition::Block(target) => {
+                let head = self.block_number().await?;
+                // a single anvil_mine covers the whole distance
+                let blocks = target.saturating_sub(head).min(max_blocks);
+                if blocks > 0 {
+                    self.mine(blocks).await?;
+                    mined = blocks;
+                }
+                self.block_number().await? >= target
+            }
+            MineCondition::Receipt(hash) => loop {
+                let receipt = rpc(provider_ws.get_transaction_receipt(hash))
+                    .await?
+                    .map_err(|e| e.to_string())?;
+                if receipt.is_some() {
+                    break true;
+                }
+                if mined == max_blocks {
+                    break false;
+                }
+                self.mine(1).await?;
+                mined += 1;
+            },
+        };
+        let block_number = self.block_number().await?;
+        self.logs.send(
+            LogStream::Manager,
+            format!(
+                "mined {} blocks until {}{}",
+                mined,
+                condition,
+                if reached { "" } else { ", gave up" }
+            ),
+        );
+        Ok(MineUntilResult {
+            mined,
+            reached,
+            block_number,
+        })
+    }
+
+    pub async fn load_state(&self, state: &str) -> Result<(), String> {
+        let provider_ws = &self.provider_ws;
+        let params = serde_json::value::to_raw_value(&(state,)).map_err(|e| e.to_string())?;
+        rpc(provider_ws.raw_request_dyn("anvil_loadState".into(), &params))
+            .await?
+            .map_err(|e| format!("Failed to load state: {}", e))?;
+        Ok(())
+    }
+
+    /// Sends 1 wei from the first prefunded account to the second through
+    /// anvil's unlocked accounts and returns the transaction hash
+    pub async fn send_test_transaction(&self) -> Result<String, String> {
+        let provider_ws = &self.provider_ws;
+        let accounts = rpc(provider_ws.get_accounts())
+            .await?
+            .map_err(|e| e.to_string())?;
+        let [from, to, ..] = accounts[..] else {
+            return Err("anvil needs at least two prefunded accounts".into());
+        };
+        let tx = TransactionRequest::default()
+            .from(from)
+            .to(to)
+            .value(U256::from(1));
+        let pending = rpc(provider_ws.send_transaction(tx))
+            .await?
+            .map_err(|e| format!("Failed to send test transaction: {}", e))?;
+        Ok(pending.tx_hash().to_string())
+    }
+
+    /// Raw `debug_traceTransaction` result, only detailed when the chain runs
+    /// with `--steps-tracing`
+    pub async fn trace_transaction(
+        &self,
+        transaction_hash: String,
+    ) -> Result<serde_json::Value, String> {
+        let provider_ws = &self.provider_ws;
+        let tx_hash = TxHash::from_str(transaction_hash.as_str())
+            .map_err(|e| format!("Invalid transaction hash: {}", e))?;
+        let params = serde_json::value::to_raw_value(&(tx_hash,)).map_err(|e| e.to_string())?;
+        let trace = rpc(provider_ws.raw_request_dyn("debug_traceTransaction".into(), &params))
+            .await?
+            .map_err(|e| e.to_string())?;
+        serde_json::from_str(trace.get()).map_err(|e| e.to_string())
+    }
+
+    /// Sends one JSON-RPC call for the proxy as is and answers with the
+    /// response object, anvil's errors included
+    pub async fn forward(
+        &self,
+        id: serde_json::Value,
+        method: String,
+        params: &serde_json::value::RawValue,
+    ) -> serde_json::Value {
+        match rpc(self.provider_ws.raw_request_dyn(method.into(), params)).await {
+            Ok(Ok(result)) => serde_json::json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Ok(Err(e)) => match e.as_error_resp() {
+                Some(payload) => {
+                    serde_json::json!({ "jsonrpc": "2.0", "id": id, "error": payload })
+                }
+                None => error_response(id, INTERNAL_ERROR, e.to_string()),
+            },
+            Err(e) => error_response(id, INTERNAL_ERROR, e),
+        }
+    }
+
+    /// Sends recorded transactions with `eth_sendRawTransaction` in order,
+    /// stopping at the first one anvil rejects
+    pub async fn replay(
+        &self,
+        transactions: &[RecordedTransaction],
+    ) -> Result<ReplayResult, String> {
+        let provider_ws = &self.provider_ws;
+        let mut result = ReplayResult::default();
+        for tx in transactions {
+            let sent = match Bytes::from_str(&tx.raw) {
+                Ok(raw) => rpc(provider_ws.send_raw_transaction(&raw))
+                    .await
+                    .and_then(|r| r.map_err(|e| e.to_string())),
+                Err(e) => Err(e.to_string()),
+            };
+            match sent {
+                Ok(pending) => result.sent.push(pending.tx_hash().to_string()),
+                Err(e) => {
+                    result.error = Some(format!("transaction {} failed: {}", tx.hash, e));
+                    break;
+                }
+            }
+        }
+        self.logs.send(
+            LogStream::Manager,
+            format!(
+                "replayed {} of {} transactions",
+                result.sent.len(),
+                transactions.len()
+            ),
+        );
+        Ok(result)
+    }
+
+    /// Raw `eth_getBlockByNumber` result with full transactions, for tools
+    /// that expect the standard JSON-RPC shape
+    pub async fn get_rpc_block(&self, block_number: u64) -> Result<serde_json::Value, String> {
+        let provider_ws = &self.provider_ws;
+        let params = serde_json::value::to_raw_value(&(format!("{:#x}", block_number), true))
+            .map_err(|e| e.to_string())?;
+        let block = rpc(provider_ws.raw_request_dyn("eth_getBlockByNumber".into(), &params))
+            .await?
+            .map_err(|e| format!("Failed to get block: {}", e))?;
+        match serde_json::from_str(block.get()).map_err(|e| e.to_string())? {
+            serde_json::Value::Null => Err(format!("Block {} not found", block_number)),
+            block => Ok(block),
+        }
+    }
+
+    /// Raw `eth_getTransactionByHash` result
+    pub async fn get_rpc_transaction(
+        &self,
+        transaction_hash: String,
+    ) -> Result<serde_json::Value, String> {
+        let provider_ws = &self.provider_ws;
+        let tx_hash = TxHash::from_str(transaction_hash.as_str())
+            .map_err(|e| format!("Invalid transaction hash: {}", e))?;
+        let params = serde_json::value::to_raw_value(&(tx_hash,)).map_err(|e| e.to_string())?;
+        let tx = rpc(provider_ws.raw_request_dyn("eth_getTransactionByHash".into(), &params))
+            .await?
+            .map_err(|e| format!("Failed to get transaction: {}", e))?;
+        match serde_json::from_str(tx.get()).map_err(|e| e.to_string())? {
+            serde_json::Value::Null => Err(format!("Transaction {} not found", transaction_hash)),
+            tx => Ok(tx),
+        }
+    }
+
     pub async fn get_transaction(&self, transaction_hash: String) -> Result<Transaction, String> {
-        let provider_ws = self.provider_ws.clone().unwrap();
+        let provider_ws = &self.provider_ws;
 
-        let tx_hash = TxHash::from_str(transaction_hash.as_str()).unwrap();
-        let tx = provider_ws
-            .get_transaction_by_hash(tx_hash)
-            .await
-            .unwrap()
-            .unwrap();
+        let tx_hash = TxHash::from_str(transaction_hash.as_str())
+            .map_err(|e| format!("Invalid transaction hash: {}", e))?;
+        let tx = rpc(provider_ws.get_transaction_by_hash(tx_hash))
+            .await?
+            .map_err(|e| format!("Failed to get transaction: {}", e))?
+            .ok_or_else(|| format!("Transaction {} not found", transaction_hash))?;
 
         Ok(Transaction {
             hash: transaction_hash,
-            block_number: tx.block_number.unwrap(),
-            index: tx.transaction_index.unwrap(),
+            block_number: tx.block_number,
+            index: tx.transaction_index,
             from: tx.as_recovered().signer().to_string(),
+            to: tx.to().map(|to| to.to_string()),
+            value: tx.value().to_string(),
+            gas: tx.gas_limit(),
+            input: tx.input().to_string(),
         })
     }
+
+    pub async fn get_logs(&self, filter: &LogFilter) -> Result<Vec<Log>, String> {
+        let provider_ws = &self.provider_ws;
+
+        let mut rpc_filter = Filter::new().from_block(filter.from_block.unwrap_or(0));
+        rpc_filter = match filter.to_block {
+            Some(to_block) => rpc_filter.to_block(to_block),
+            None => rpc_filter.to_block(BlockNumberOrTag::Latest),
+        };
+        if let Some(address) = filter.address.as_ref().filter(|a| !a.trim().is_empty()) {
+            let address =
+                Address::from_str(address.trim()).map_err(|e| format!("Invalid address: {}", e))?;
+            rpc_filter = rpc_filter.address(address);
+        }
+        for (position, topic) in filter.topics().into_iter().enumerate() {
+            let Some(topic) = topic.as_ref().filter(|t| !t.trim().is_empty()) else {
+                continue;
+            };
+            let topic = B256::from_str(topic.trim())
+                .map_err(|e| format!("Invalid topic{}: {}", position, e))?;
+            rpc_filter = match position {
+                0 => rpc_filter.event_signature(topic),
+                1 => rpc_filter.topic1(topic),
+                2 => rpc_filter.topic2(topic),
+                _ => rpc_filter.topic3(topic),
+            };
+        }
+
+        let logs = rpc(provider_ws.get_logs(&rpc_filter))
+            .await?
+            .map_err(|e| format!("Failed to get logs: {}", e))?;
+
+        Ok(logs
+            .into_iter()
+            .map(|log| Log {
+                address: log.address().to_string(),
+                topics: log.topics().iter().map(|t| t.to_string()).collect(),
+                data: log.data().data.to_string(),
+                block_number: log.block_number.unwrap_or_default(),
+                transaction_hash: log.transaction_hash.unwrap_or_default().to_string(),
+                transaction_index: log.transaction_index.unwrap_or_default(),
+                log_index: log.log_index.unwrap_or_default(),
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block_range_bounds() {
+        assert!(check_block_range(3, 3).is_ok());
+        assert!(check_block_range(0, MAX_BLOCK_RANGE - 1).is_ok());
+        assert!(check_block_range(4, 3).is_err());
+        assert!(check_block_range(0, MAX_BLOCK_RANGE).is_err());
+        assert!(check_block_range(u64::MAX - MAX_BLOCK_RANGE, u64::MAX).is_err());
+    }
 }
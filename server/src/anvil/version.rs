@@ -0,0 +1,89 @@
+use std::ffi::OsStr;
+use std::fmt;
+use tokio::process::Command;
+
+/// Release of the `anvil` binary on `PATH`, without pre-release or build
+/// suffixes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct AnvilVersion {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+}
+
+impl AnvilVersion {
+    const fn new(major: u64, minor: u64, patch: u64) -> Self {
+        AnvilVersion {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// Runs `anvil --version`, `None` when anvil isn't installed or prints
+    /// something unexpected
+    pub async fn detect() -> Option<AnvilVersion> {
+        let output = Command::new("anvil").arg("--version").output().await.ok()?;
+        Self::parse(&String::from_utf8_lossy(&output.stdout))
+    }
+
+    /// First `x.y.z` in the output, which covers both
+    /// `anvil 0.2.0 (c2233ec 2024-03-28)` and `anvil Version: 1.2.3-stable`
+    pub fn parse(output: &str) -> Option<AnvilVersion> {
+        output.split_whitespace().find_map(|word| {
+            let core = word.split(['-', '+']).next()?;
+            let mut parts = core.split('.').map(|p| p.parse::<u64>().ok());
+            match (parts.next(), parts.next(), parts.next(), parts.next()) {
+                (Some(Some(major)), Some(Some(minor)), Some(Some(patch)), None) => {
+                    Some(AnvilVersion::new(major, minor, patch))
+                }
+                _ => None,
+            }
+        })
+    }
+
+    /// Fails with the first argument this version doesn't know, so an old
+    /// anvil is reported before it is spawned rather than by its usage text
+    pub fn check<'a>(&self, args: impl IntoIterator<Item = &'a OsStr>) -> Result<(), String> {
+        let args: Vec<String> = args
+            .into_iter()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect();
+        for (i, arg) in args.iter().enumerate() {
+            // both `--flag value` and `--flag=value`
+            let (flag, value) = match arg.split_once('=') {
+                Some((flag, value)) => (flag, Some(value)),
+                None => (arg.as_str(), args.get(i + 1).map(String::as_str)),
+            };
+            let unsupported = CAPABILITIES.iter().find(|(f, v, since)| {
+                *f == flag
+                    && v.is_none_or(|v| value.is_some_and(|value| value.eq_ignore_ascii_case(v)))
+                    && self < since
+            });
+            if let Some((flag, value, since)) = unsupported {
+                let option = match value {
+                    Some(value) => format!("{} {}", flag, value),
+                    None => flag.to_string(),
+                };
+                return Err(format!(
+                    "your anvil version ({}) doesn't support {}, it needs {} or newer",
+                    self, option, since
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for AnvilVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Flags, or flag values when given, with the first anvil release that
+/// accepts them. Anything not listed is passed through unchecked.
+const CAPABILITIES: &[(&str, Option<&str>, AnvilVersion)] = &[
+    ("--hardfork", Some("prague"), AnvilVersion::new(1, 0, 0)),
+    ("--hardfork", Some("osaka"), AnvilVersion::new(1, 4, 0)),
+];
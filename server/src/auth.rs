@@ -0,0 +1,71 @@
+use crate::AppState;
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{header, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::net::SocketAddr;
+
+/// The routes the client opens with `EventSource`
+fn is_stream(path: &str) -> bool {
+    path.ends_with("stream") || path == "/api/chains/events"
+}
+
+/// Rejects API requests that don't carry `LOCALCHAIN_TOKEN` when it is set.
+/// `EventSource` can't send headers, so the token is also accepted as a
+/// `token` query param for the stream routes.
+pub async fn require_token(State(state): State<AppState>, req: Request, next: Next) -> Response {
+    let Some(expected) = state.token.as_deref() else {
+        return next.run(req).await;
+    };
+
+    let from_header = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+    let from_query = req
+        .uri()
+        .query()
+        .filter(|_| is_stream(req.uri().path()))
+        .and_then(|q| {
+            form_urlencoded::parse(q.as_bytes())
+                .find(|(key, _)| key == "token")
+                .map(|(_, token)| token.into_owned())
+        });
+
+    if from_header.or(from_query.as_deref()) == Some(expected) {
+        next.run(req).await
+    } else {
+        (StatusCode::UNAUTHORIZED, "unauthorized").into_response()
+    }
+}
+
+/// Admin routes answer loopback clients only, unless `LOCALCHAIN_TOKEN`
+/// already guards them
+pub async fn require_local_without_token(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    req: Request,
+    next: Next,
+) -> Response {
+    if state.token.is_none() && !addr.ip().is_loopback() {
+        (
+            StatusCode::FORBIDDEN,
+            "admin routes are localhost only without LOCALCHAIN_TOKEN",
+        )
+            .into_response()
+    } else {
+        next.run(req).await
+    }
+}
+
+/// Rejects mutating routes with 403 when `LOCALCHAIN_READONLY` is set
+pub async fn reject_read_only(State(state): State<AppState>, req: Request, next: Next) -> Response {
+    if state.read_only {
+        (StatusCode::FORBIDDEN, "server is in read-only mode").into_response()
+    } else {
+        next.run(req).await
+    }
+}
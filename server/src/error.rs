@@ -0,0 +1,84 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use std::fmt;
+
+/// Why a `ChainsManager` call failed, handlers answer with the matching
+/// status and the message as body
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ManagerError {
+    /// No chain has this id
+    NotFound(u64),
+    /// The chain exists but anvil isn't up
+    NotRunning(u64),
+    /// Another chain already has this id or name
+    AlreadyExists(String),
+    /// `LOCALCHAIN_MAX_CHAINS` chains exist already
+    LimitReached(usize),
+    /// Every request slot of the chain stayed taken for the RPC timeout
+    Busy(u64),
+    /// A start brought up anvil, but it reports another chain id than the
+    /// config. The chain is left in `Error` with anvil running.
+    ChainIdMismatch { expected: u64, actual: u64 },
+    /// Invalid config or a failure from anvil itself
+    Backend(String),
+}
+
+impl fmt::Display for ManagerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ManagerError::NotFound(id) => write!(f, "chain {} not found", id),
+            ManagerError::NotRunning(id) => write!(f, "chain {} is not running", id),
+            ManagerError::AlreadyExists(what) => write!(f, "{} already exists", what),
+            ManagerError::LimitReached(max) => write!(f, "chain limit reached ({})", max),
+            ManagerError::Busy(id) => write!(f, "chain {} is busy, try again", id),
+            ManagerError::ChainIdMismatch { expected, actual } => write!(
+                f,
+                "chain id mismatch: configured {}, anvil reports {}",
+                expected, actual
+            ),
+            ManagerError::Backend(message) => f.write_str(message),
+        }
+    }
+}
+
+impl From<String> for ManagerError {
+    fn from(message: String) -> Self {
+        ManagerError::Backend(message)
+    }
+}
+
+impl IntoResponse for ManagerError {
+    fn into_response(self) -> Response {
+        let status = match self {
+            ManagerError::NotFound(_) => StatusCode::NOT_FOUND,
+            ManagerError::NotRunning(_)
+            | ManagerError::AlreadyExists(_)
+            | ManagerError::ChainIdMismatch { .. } => StatusCode::CONFLICT,
+            ManagerError::LimitReached(_) => StatusCode::TOO_MANY_REQUESTS,
+            ManagerError::Busy(_) => StatusCode::SERVICE_UNAVAILABLE,
+            ManagerError::Backend(_) => StatusCode::BAD_REQUEST,
+        };
+        (status, self.to_string()).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn chain_id_mismatch_is_a_conflict_naming_both_ids() {
+        let response = ManagerError::ChainIdMismatch {
+            expected: 31337,
+            actual: 1,
+        }
+        .into_response();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body, "chain id mismatch: configured 31337, anvil reports 1");
+    }
+}
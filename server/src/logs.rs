@@ -0,0 +1,258 @@
+use shared::types::log_line::{LogLine, LogStream, DEFAULT_PREFIX};
+use shared::types::log_lines::LogLinesResponse;
+use shared::types::log_search::{LogMatch, LogSearchResponse};
+use std::collections::VecDeque;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::broadcast;
+
+/// Number of lines kept per chain, overridable with `LOCALCHAIN_LOG_BUFFER`
+const DEFAULT_CAPACITY: usize = 1000;
+
+pub fn default_capacity() -> usize {
+    std::env::var("LOCALCHAIN_LOG_BUFFER")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|c| *c > 0)
+        .unwrap_or(DEFAULT_CAPACITY)
+}
+
+/// A chain's log file is rotated once it reaches this size
+const LOG_FILE_MAX_BYTES: u64 = 10 * 1024 * 1024;
+/// Rotated files kept next to the current one, as `<id>.log.1` (newest) to
+/// `<id>.log.3`
+const LOG_FILE_KEEP: usize = 3;
+
+/// Template put before each plain text log line, overridable with
+/// `LOCALCHAIN_LOG_PREFIX`. Set but empty means no prefix.
+pub fn prefix_template() -> String {
+    std::env::var("LOCALCHAIN_LOG_PREFIX").unwrap_or_else(|_| DEFAULT_PREFIX.to_string())
+}
+
+/// Keeps the most recent log lines of a chain and fans new ones out to
+/// live subscribers
+pub struct LogBuffer {
+    chain_id: u64,
+    lines: Mutex<VecDeque<LogLine>>,
+    capacity: usize,
+    /// Lines logged so far, only changed while `lines` is locked. The
+    /// oldest buffered line has index `sent - lines.len()`.
+    sent: AtomicU64,
+    tx: broadcast::Sender<LogLine>,
+    /// Every line is also appended here when `LOCALCHAIN_LOG_DIR` is set
+    file: Option<Mutex<LogFile>>,
+}
+
+/// `<dir>/<id>.log`, rotated by size
+struct LogFile {
+    path: PathBuf,
+    file: File,
+    size: u64,
+    prefix: String,
+    /// `LogBuffer` index of the first line in the current file, earlier
+    /// lines went to a rotated one
+    first_index: u64,
+}
+
+impl LogFile {
+    fn open(path: PathBuf) -> std::io::Result<Self> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata()?.len();
+        Ok(Self {
+            path,
+            file,
+            size,
+            prefix: prefix_template(),
+            first_index: 0,
+        })
+    }
+
+    fn rotated(&self, n: usize) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{}", n));
+        path.into()
+    }
+
+    fn write(&mut self, line: &LogLine, index: u64) -> std::io::Result<()> {
+        let text = format!("{}\n", line.to_plain(&self.prefix));
+        if self.size > 0 && self.size + text.len() as u64 > LOG_FILE_MAX_BYTES {
+            for n in (1..LOG_FILE_KEEP).rev() {
+                let _ = std::fs::rename(self.rotated(n), self.rotated(n + 1));
+            }
+            std::fs::rename(&self.path, self.rotated(1))?;
+            self.file = File::create(&self.path)?;
+            self.size = 0;
+            self.first_index = index;
+        }
+        self.file.write_all(text.as_bytes())?;
+        self.size += text.len() as u64;
+        Ok(())
+    }
+}
+
+impl LogBuffer {
+    pub fn new(chain_id: u64, capacity: usize) -> Self {
+        let (tx, _rx) = broadcast::channel(1024);
+        Self {
+            chain_id,
+            // grows on demand past the default, large retentions are rare
+            lines: Mutex::new(VecDeque::with_capacity(capacity.min(DEFAULT_CAPACITY))),
+            capacity,
+            sent: AtomicU64::new(0),
+            tx,
+            file: None,
+        }
+    }
+
+    /// Also appends every line to `<dir>/<chain id>.log`. Logging carries on
+    /// in memory only if the file can't be opened.
+    pub fn with_log_dir(mut self, dir: &Path) -> Self {
+        let path = dir.join(format!("{}.log", self.chain_id));
+        match LogFile::open(path.clone()) {
+            Ok(file) => self.file = Some(Mutex::new(file)),
+            Err(e) => println!("could not open log file {}: {}", path.display(), e),
+        }
+        self
+    }
+
+    pub fn send(&self, stream: LogStream, text: String) {
+        let line = LogLine {
+            ts: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or_default(),
+            stream,
+            chain_id: self.chain_id,
+            text,
+        };
+        let mut lines = self.lines.lock().unwrap();
+        if lines.len() == self.capacity {
+            lines.pop_front();
+        }
+        lines.push_back(line.clone());
+        let index = self.sent.fetch_add(1, Ordering::Relaxed);
+        if let Some(file) = &self.file {
+            if let Err(e) = file.lock().unwrap().write(&line, index) {
+                println!("[{}] could not write log file: {}", self.chain_id, e);
+            }
+        }
+        let _ = self.tx.send(line);
+    }
+
+    /// Case-insensitive substring search over the buffered lines rendered
+    /// with `prefix`, keeping the last `limit` matches. Each match carries the
+    /// line's index, the one `since` takes.
+    pub fn search(&self, query: &str, limit: usize, prefix: &str) -> LogSearchResponse {
+        let query = query.to_lowercase();
+        let lines = self.lines.lock().unwrap();
+        let oldest = self.sent.load(Ordering::Relaxed) - lines.len() as u64;
+        let mut matches: VecDeque<LogMatch> = VecDeque::with_capacity(limit);
+        let mut total = 0;
+        for (index, line) in (oldest..).zip(lines.iter()) {
+            let line = line.to_plain(prefix);
+            if !line.to_lowercase().contains(&query) {
+                continue;
+            }
+            total += 1;
+            if matches.len() == limit {
+                matches.pop_front();
+            }
+            matches.push_back(LogMatch { index, line });
+        }
+        LogSearchResponse {
+            matches: matches.into(),
+            total,
+        }
+    }
+
+    /// The buffer rendered with `prefix`. When logging to disk, the current
+    /// log file as written instead, after the buffered lines that were
+    /// rotated out of it, so a download right after a rotation still has
+    /// everything buffered.
+    pub fn dump(&self, prefix: &str) -> String {
+        // same lock order as `send`
+        let lines = self.lines.lock().unwrap();
+        let file = self.file.as_ref().and_then(|file| {
+            let file = file.lock().unwrap();
+            let contents = std::fs::read_to_string(&file.path).ok()?;
+            Some((file.first_index, contents))
+        });
+        let oldest = self.sent.load(Ordering::Relaxed) - lines.len() as u64;
+        let buffered = match &file {
+            Some((first_index, _)) => first_index.saturating_sub(oldest) as usize,
+            None => lines.len(),
+        };
+        let mut dump: String = lines
+            .iter()
+            .take(buffered)
+            .map(|line| format!("{}\n", line.to_plain(prefix)))
+            .collect();
+        if let Some((_, contents)) = file {
+            dump.push_str(&contents);
+        }
+        dump
+    }
+
+    /// Up to `limit` buffered lines (all when `None`) from index `since` on,
+    /// or from the oldest line kept when `None`, rendered with `prefix`. An
+    /// index that already fell out of the buffer starts at the oldest line
+    /// kept, one past the newest returns nothing.
+    pub fn since(
+        &self,
+        since: Option<u64>,
+        limit: Option<usize>,
+        prefix: &str,
+    ) -> LogLinesResponse {
+        let lines = self.lines.lock().unwrap();
+        let sent = self.sent.load(Ordering::Relaxed);
+        let oldest = sent - lines.len() as u64;
+        let since = since.unwrap_or(oldest);
+        let start = since.clamp(oldest, sent);
+        let lines: Vec<String> = lines
+            .iter()
+            .skip((start - oldest) as usize)
+            .take(limit.unwrap_or(usize::MAX))
+            .map(|line| line.to_plain(prefix))
+            .collect();
+        LogLinesResponse {
+            next_index: start + lines.len() as u64,
+            lines,
+            dropped: oldest.saturating_sub(since),
+        }
+    }
+
+    /// Returns the last `tail` buffered lines (all when `None`) together with
+    /// a receiver that picks up right after them
+    pub fn subscribe(&self, tail: Option<usize>) -> (Vec<LogLine>, broadcast::Receiver<LogLine>) {
+        let lines = self.lines.lock().unwrap();
+        let skip = lines.len().saturating_sub(tail.unwrap_or(lines.len()));
+        let backlog = lines.iter().skip(skip).cloned().collect();
+        (backlog, self.tx.subscribe())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn search_indexes_match_since_after_wrapping() {
+        let logs = LogBuffer::new(1, 10);
+        for n in 0..25 {
+            logs.send(LogStream::Stdout, format!("line {}", n));
+        }
+        let found = logs.search("line 17", 10, "");
+        assert_eq!(found.total, 1);
+        let hit = &found.matches[0];
+        assert_eq!(hit.index, 17);
+        let fetched = logs.since(Some(hit.index), Some(1), "");
+        assert_eq!(fetched.lines, std::slice::from_ref(&hit.line));
+    }
+}
@@ -1,243 +1,1451 @@
-use crate::anvil::process::AnvilProcess;
+use crate::anvil::process::{
+    rpc_timeout, start_timeout, AnvilClient, AnvilProcess, LiveStats, MineCondition, SharedClient,
+};
+use crate::anvil::version::AnvilVersion;
+use crate::error::ManagerError;
+use crate::logs::LogBuffer;
+use crate::rpc::RpcPolicy;
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
-    response::{sse, Html, IntoResponse, Sse},
-    routing::{get, post},
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    middleware,
+    response::{sse, Html, IntoResponse, Response, Sse},
+    routing::{any, get, post},
     Json, Router,
 };
 use futures::Stream;
+use schemars::JsonSchema;
+use serde::Deserialize;
 use shared::types::{
+    availability::Availability,
     block::Block,
     block_response::BlockResponse,
-    chain_config::{ChainConfig, ChainStatus},
+    chain_config::{ChainConfig, ChainStatus, AUTO_PORT, DEFAULT_HOST},
+    chain_event::{ChainEvent, ChainEventKind},
+    chain_result::ChainResult,
+    chain_status_event::ChainStatusEvent,
+    chains_summary::ChainsSummary,
+    dev_only_private_key::DevOnlyPrivateKey,
+    health::Health,
+    log_filter::LogFilter,
+    log_line::{LogLine, LogStream},
+    log_lines::LogLinesResponse,
+    log_search::LogSearchResponse,
+    logs_response::LogsResponse,
+    mine_until_result::MineUntilResult,
+    recording::Recording,
+    replay_result::ReplayResult,
+    resource_usage::ResourceUsage,
     transaction::Transaction,
     transaction_response::TransactionResponse,
 };
 use std::convert::Infallible;
 use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::{collections::HashMap, net::SocketAddr, path::PathBuf, sync::Arc};
-use tokio::sync::{broadcast, Mutex};
+use tokio::sync::{broadcast, watch, Mutex, OwnedSemaphorePermit, Semaphore};
 use tokio_stream::{wrappers::BroadcastStream, StreamExt};
 use tower_http::services::ServeDir;
 
 mod anvil;
+mod auth;
+mod error;
+mod logs;
+mod openapi;
+mod rpc;
 
 #[derive(Clone)]
 struct AppState {
     client_dist: PathBuf,
+    /// `client_dist` has an `index.html`, checked once at startup
+    client_built: bool,
     manager: Arc<ChainsManager>,
+    /// Bearer token required on `/api` routes, disabled when `None`
+    token: Option<String>,
+    /// Rejects every route that changes chains when set
+    read_only: bool,
+    /// Serves anvil's private keys on `/api/chains/:id/keys` when set
+    expose_keys: bool,
+    /// Put before each line of the plain log stream
+    log_prefix: Arc<str>,
+    /// Set once the server is shutting down, which ends open streams
+    shutdown: Arc<watch::Sender<bool>>,
+    /// `anvil --version` at startup, `None` when it couldn't be run or parsed
+    anvil_version: Option<AnvilVersion>,
+    /// Methods `/api/chains/:id/rpc` forwards
+    rpc_policy: Arc<RpcPolicy>,
 }
 
 struct ChainEntry {
     id: u64,
     config: ChainConfig,
-    log_tx: Arc<broadcast::Sender<String>>,
+    logs: Arc<LogBuffer>,
     block_tx: Arc<broadcast::Sender<Block>>,
+    /// Shared with the process so background tasks can flag failures
+    status_tx: Arc<watch::Sender<ChainStatus>>,
+    /// Shared with the process, 0 until anvil has answered `eth_chainId`
+    actual_chain_id: Arc<AtomicU64>,
+    /// Shared with the process, 0 while stopped
+    started_at: Arc<AtomicU64>,
+    /// Shared with the process, 0 until a pinned fork first starts
+    pinned_fork_block: Arc<AtomicU64>,
+    /// Shared with the process' block subscription
+    stats: Arc<LiveStats>,
+    /// Shared with the process, set while anvil is up
+    client: SharedClient,
+    /// Slots for calls that go to anvil, see `running_client`
+    rpc_permits: Arc<Semaphore>,
+    process: Arc<Mutex<AnvilProcess>>,
+}
+
+/// See `ChainsManager::entry_handles`
+struct EntryHandles {
     process: Arc<Mutex<AnvilProcess>>,
+    status_tx: Arc<watch::Sender<ChainStatus>>,
+    logs: Arc<LogBuffer>,
+}
+
+impl ChainEntry {
+    fn config(&self) -> ChainConfig {
+        ChainConfig {
+            status: *self.status_tx.borrow(),
+            actual_chain_id: Some(self.actual_chain_id.load(Ordering::Relaxed))
+                .filter(|id| *id != 0),
+            started_at: Some(self.started_at.load(Ordering::Relaxed)).filter(|t| *t != 0),
+            resolved_fork_block: Some(self.pinned_fork_block.load(Ordering::Relaxed))
+                .filter(|n| *n != 0),
+            ..self.config.clone()
+        }
+    }
 }
 
 #[derive(Default)]
 struct ChainsManager {
     /// id: ChainEntry
     inner: Mutex<HashMap<u64, ChainEntry>>,
+    /// Source of `ChainConfig::created_seq`
+    next_seq: AtomicU64,
+    /// Chain configs are saved here after every change and reloaded on boot
+    state_file: Option<PathBuf>,
+    /// Held while `state_file` is written, so writes land one at a time
+    state_writer: Mutex<StateWriter>,
+    /// Accepts chain ports below 1024, for servers running as root
+    allow_privileged_ports: bool,
+    /// Each chain's log is also written to `<id>.log` in here
+    log_dir: Option<PathBuf>,
+    /// Creating more chains than this is refused
+    max_chains: Option<usize>,
+    /// Calls to anvil in flight per chain, `DEFAULT_CHAIN_CONCURRENCY` when
+    /// unset
+    chain_concurrency: Option<usize>,
+    /// Handed to every chain so starts can reject flags it doesn't support
+    anvil_version: Option<AnvilVersion>,
+    events: ChainEvents,
+}
+
+#[derive(Default)]
+struct StateWriter {
+    /// Set while `restore` recreates the chains, it writes once at the end.
+    /// Stays set when the state file exists but couldn't be read or moved
+    /// aside, so it is never overwritten.
+    paused: bool,
+    /// Saved chains that failed to restore, written back as they were so a
+    /// bad entry or a lowered chain limit doesn't delete them
+    unrestored: Vec<ChainConfig>,
+}
+
+/// Feeds `/api/chains/events`
+struct ChainEvents(broadcast::Sender<ChainEvent>);
+
+impl Default for ChainEvents {
+    fn default() -> Self {
+        ChainEvents(broadcast::channel(256).0)
+    }
+}
+
+const DEFAULT_CHAIN_CONCURRENCY: usize = 8;
+
+/// `path` with `suffix` appended to its file name
+fn with_suffix(path: &std::path::Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// Asks the OS for a free port on the chain's host, skipping ports other
+/// chains are configured on. The listener is closed before anvil binds the
+/// port, so another program could take it in between; the start then fails
+/// with "address in use" and the chain can be recreated.
+async fn free_port(
+    cfg: &ChainConfig,
+    chains: &HashMap<u64, ChainEntry>,
+) -> Result<u16, ManagerError> {
+    for _ in 0..10 {
+        let port = tokio::net::TcpListener::bind((cfg.host(), AUTO_PORT))
+            .await
+            .and_then(|listener| listener.local_addr())
+            .map_err(|e| format!("could not pick a free port: {}", e))?
+            .port();
+        if !chains.values().any(|c| c.config.port == port) {
+            return Ok(port);
+        }
+    }
+    Err(ManagerError::Backend("could not pick a free port".into()))
 }
 
 impl ChainsManager {
+    async fn process(&self, id: &u64) -> Result<Arc<Mutex<AnvilProcess>>, ManagerError> {
+        let map = self.inner.lock().await;
+        let Some(entry) = map.get(id) else {
+            return Err(ManagerError::NotFound(*id));
+        };
+        Ok(entry.process.clone())
+    }
+
+    /// Client for calls that need anvil to answer RPC, taken without the
+    /// process lock so calls only queue on the permits. Callers hold the
+    /// permit for the whole call so a request storm queues here instead of on
+    /// anvil, and gives up with `Busy` after the RPC timeout.
+    async fn running_client(
+        &self,
+        id: &u64,
+    ) -> Result<(AnvilClient, OwnedSemaphorePermit), ManagerError> {
+        let map = self.inner.lock().await;
+        let Some(entry) = map.get(id) else {
+            return Err(ManagerError::NotFound(*id));
+        };
+        if *entry.status_tx.borrow() != ChainStatus::Running {
+            return Err(ManagerError::NotRunning(*id));
+        }
+        let (slot, permits) = (entry.client.clone(), entry.rpc_permits.clone());
+        drop(map);
+        let permit = match tokio::time::timeout(rpc_timeout(), permits.acquire_owned()).await {
+            Ok(Ok(permit)) => permit,
+            _ => return Err(ManagerError::Busy(*id)),
+        };
+        let client = slot.read().unwrap().clone();
+        match client {
+            Some(client) => Ok((client, permit)),
+            None => Err(ManagerError::NotRunning(*id)),
+        }
+    }
+
+    /// Like `running_client`, for changes to the running process itself.
+    /// These hold the process lock, so they also wait out a start or stop
+    /// in progress.
+    async fn running_process(
+        &self,
+        id: &u64,
+    ) -> Result<(Arc<Mutex<AnvilProcess>>, OwnedSemaphorePermit), ManagerError> {
+        let map = self.inner.lock().await;
+        let Some(entry) = map.get(id) else {
+            return Err(ManagerError::NotFound(*id));
+        };
+        if *entry.status_tx.borrow() != ChainStatus::Running {
+            return Err(ManagerError::NotRunning(*id));
+        }
+        let (process, permits) = (entry.process.clone(), entry.rpc_permits.clone());
+        drop(map);
+        match tokio::time::timeout(rpc_timeout(), permits.acquire_owned()).await {
+            Ok(Ok(permit)) => Ok((process, permit)),
+            _ => Err(ManagerError::Busy(*id)),
+        }
+    }
+
+    /// Recreates the chains in `state_file` and then the `declared` ones from
+    /// `LOCALCHAIN_CONFIG`. A declared chain whose id is already stored
+    /// replaces only the fields it sets, so runtime changes such as notes or
+    /// pins survive. Chains with `auto_start` are started afterwards.
+    /// Returns the number of chains restored
+    async fn restore(&self, declared: Vec<serde_json::Map<String, serde_json::Value>>) -> usize {
+        self.state_writer.lock().await.paused = true;
+        let (mut configs, writable) = match self.read_state_file().await {
+            Some(configs) => (configs, true),
+            None => (vec![], false),
+        };
+        let saved: Vec<u64> = configs.iter().map(|c| c.id).collect();
+        for fields in declared {
+            let id = fields.get("id").and_then(|id| id.as_u64());
+            let stored = configs.iter().position(|c| Some(c.id) == id);
+            let mut merged = match stored.map(|i| serde_json::to_value(&configs[i])) {
+                Some(Ok(serde_json::Value::Object(stored))) => stored,
+                _ => serde_json::Map::new(),
+            };
+            merged.extend(fields);
+            match serde_json::from_value::<ChainConfig>(merged.into()) {
+                Ok(cfg) => match stored {
+                    Some(i) => configs[i] = cfg,
+                    None => configs.push(cfg),
+                },
+                Err(e) => println!("ignoring chain {:?} in config file: {}", id, e),
+            }
+        }
+        let auto_start: Vec<u64> = configs
+            .iter()
+            .filter(|c| c.auto_start)
+            .map(|c| c.id)
+            .collect();
+        let mut restored = 0;
+        let mut unrestored = vec![];
+        for (cfg, result) in configs.clone().into_iter().zip(self.import(configs).await) {
+            match result.error {
+                Some(error) => {
+                    println!("[{}] not restored: {}", result.name, error);
+                    if saved.contains(&cfg.id) {
+                        unrestored.push(cfg);
+                    }
+                }
+                None => restored += 1,
+            }
+        }
+        {
+            let mut writer = self.state_writer.lock().await;
+            writer.paused = !writable;
+            writer.unrestored = unrestored;
+        }
+        self.persist().await;
+        for id in auto_start {
+            if let Err(e) = self.start(&id).await {
+                println!("[{}] auto start failed: {}", id, e);
+            }
+        }
+        restored
+    }
+
+    /// `None` when the file exists but can't be used and must not be
+    /// overwritten. One that doesn't parse is renamed to `<file>.invalid`
+    /// first, so a fresh one can be started next to it.
+    async fn read_state_file(&self) -> Option<Vec<ChainConfig>> {
+        let Some(path) = &self.state_file else {
+            return Some(vec![]);
+        };
+        let json = match tokio::fs::read_to_string(path).await {
+            Ok(json) => json,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Some(vec![]),
+            Err(e) => {
+                println!(
+                    "could not read state file {}, not saving chains: {}",
+                    path.display(),
+                    e
+                );
+                return None;
+            }
+        };
+        let e = match serde_json::from_str(&json) {
+            Ok(configs) => return Some(configs),
+            Err(e) => e,
+        };
+        let backup = with_suffix(path, ".invalid");
+        match tokio::fs::rename(path, &backup).await {
+            Ok(()) => {
+                println!(
+                    "state file {} is invalid, moved to {}: {}",
+                    path.display(),
+                    backup.display(),
+                    e
+                );
+                Some(vec![])
+            }
+            Err(rename) => {
+                println!(
+                    "state file {} is invalid, not saving chains: {} (could not move it aside: {})",
+                    path.display(),
+                    e,
+                    rename
+                );
+                None
+            }
+        }
+    }
+
+    /// Tells `/api/chains/events` subscribers about a change, nobody
+    /// listening is fine
+    fn notify(&self, kind: ChainEventKind, cfg: &ChainConfig) {
+        let _ = self.events.0.send(ChainEvent {
+            kind,
+            chain: cfg.redacted(),
+        });
+    }
+
+    /// Writes every chain config to `state_file`, API keys included. The
+    /// file is written next to it and renamed over it, so a crash leaves
+    /// either the old or the new one.
+    async fn persist(&self) {
+        let Some(path) = &self.state_file else {
+            return;
+        };
+        let writer = self.state_writer.lock().await;
+        if writer.paused {
+            return;
+        }
+        let mut chains = self.list().await;
+        let kept: Vec<ChainConfig> = writer
+            .unrestored
+            .iter()
+            .filter(|c| !chains.iter().any(|chain| chain.id == c.id))
+            .cloned()
+            .collect();
+        chains.extend(kept);
+        let json = serde_json::to_string_pretty(&chains).unwrap_or_default();
+        let tmp = with_suffix(path, ".tmp");
+        let written = match tokio::fs::write(&tmp, json).await {
+            Ok(()) => tokio::fs::rename(&tmp, path).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = written {
+            println!("could not write state file {}: {}", path.display(), e);
+        }
+    }
+
     async fn list(&self) -> Vec<ChainConfig> {
         let map = self.inner.lock().await;
-        map.values().map(|c| c.config.clone()).collect()
+        let mut list: Vec<ChainConfig> = map.values().map(|c| c.config()).collect();
+        list.sort_by_key(|c| c.created_seq);
+        list
     }
 
-    async fn create(&self, cfg: ChainConfig) -> Result<u64, String> {
+    /// Returns the config as stored, including server assigned fields
+    async fn create(&self, mut cfg: ChainConfig) -> Result<ChainConfig, ManagerError> {
+        cfg.validate(self.allow_privileged_ports)?;
         let mut map = self.inner.lock().await;
         if map.contains_key(&cfg.id) {
-            return Err("name already exists".into());
+            return Err(ManagerError::AlreadyExists(format!("chain id {}", cfg.id)));
         }
-        let (log_tx, _log_rx) = broadcast::channel(1024);
-        let (block_tx, _block_rx) = broadcast::channel(1024);
-        let log_tx = Arc::new(log_tx);
-        let block_tx = Arc::new(block_tx);
-        let process = AnvilProcess::new(
-            cfg.name.clone(),
+        if let Some(max) = self.max_chains.filter(|max| map.len() >= *max) {
+            return Err(ManagerError::LimitReached(max));
+        }
+        if cfg.port == AUTO_PORT {
+            cfg.port = free_port(&cfg, &map).await?;
+        }
+        cfg.created_seq = self.next_seq.fetch_add(1, Ordering::Relaxed);
+        cfg.status = ChainStatus::Stopped;
+        cfg.actual_chain_id = None;
+        cfg.started_at = None;
+        cfg.resolved_fork_block = None;
+        let mut logs = LogBuffer::new(
             cfg.id,
-            cfg.port,
-            cfg.block_time,
-            log_tx.clone(),
-            block_tx.clone(),
-            cfg.fork_url.clone(),
+            cfg.log_retention.unwrap_or_else(logs::default_capacity),
         );
+        if let Some(dir) = &self.log_dir {
+            logs = logs.with_log_dir(dir);
+        }
+        let logs = Arc::new(logs);
+        let (block_tx, _block_rx) = broadcast::channel(1024);
+        let block_tx = Arc::new(block_tx);
+        let status_tx = Arc::new(watch::Sender::new(cfg.status));
+        let mut process =
+            AnvilProcess::new(&cfg, logs.clone(), block_tx.clone(), status_tx.clone());
+        process.anvil_version = self.anvil_version;
         let entry = ChainEntry {
             id: cfg.id,
             config: cfg,
-            log_tx: log_tx,
-            block_tx: block_tx,
+            logs,
+            block_tx,
+            status_tx,
+            actual_chain_id: process.actual_chain_id.clone(),
+            started_at: process.started_at.clone(),
+            pinned_fork_block: process.pinned_fork_block.clone(),
+            stats: process.stats.clone(),
+            client: process.client.clone(),
+            rpc_permits: Arc::new(Semaphore::new(
+                self.chain_concurrency.unwrap_or(DEFAULT_CHAIN_CONCURRENCY),
+            )),
             process: Arc::new(Mutex::new(process)),
         };
-        let id = entry.id.clone();
-        map.insert(id, entry);
+        let stored = entry.config();
+        map.insert(entry.id, entry);
         drop(map);
-        Ok(id)
+        self.persist().await;
+        self.notify(ChainEventKind::Created, &stored);
+        Ok(stored)
     }
 
-    async fn start(&self, id: &u64) -> Result<(), String> {
-        let mut map = self.inner.lock().await;
-        let Some(entry) = map.get_mut(id) else {
-            return Err("not found".into());
+    /// Creates each chain in order, skipping ids that already exist
+    async fn import(&self, configs: Vec<ChainConfig>) -> Vec<ChainResult> {
+        let mut results = vec![];
+        for cfg in configs {
+            let (id, name) = (cfg.id, cfg.name.clone());
+            let result = self.create(cfg).await;
+            results.push(ChainResult {
+                id,
+                name,
+                ok: result.is_ok(),
+                error: result.err().map(|e| e.to_string()),
+            });
+        }
+        results
+    }
+
+    /// The parts of a chain's entry that starts and stops work through,
+    /// cloned so the map isn't locked while they wait on anvil
+    async fn entry_handles(&self, id: &u64) -> Result<EntryHandles, ManagerError> {
+        let map = self.inner.lock().await;
+        let Some(entry) = map.get(id) else {
+            return Err(ManagerError::NotFound(*id));
         };
-        entry.config.status = ChainStatus::Starting;
+        Ok(EntryHandles {
+            process: entry.process.clone(),
+            status_tx: entry.status_tx.clone(),
+            logs: entry.logs.clone(),
+        })
+    }
+
+    async fn start(&self, id: &u64) -> Result<(), ManagerError> {
+        // the map stays usable for other chains while this one starts
+        let entry = self.entry_handles(id).await?;
+        entry.status_tx.send_replace(ChainStatus::Starting);
+        entry.logs.send(LogStream::Manager, "starting".into());
         let mut process = entry.process.lock().await;
-        match process.start().await {
-            Ok(()) => {
-                entry.config.status = ChainStatus::Running;
+        let deadline = start_timeout();
+        let result = match tokio::time::timeout(deadline, process.start()).await {
+            Ok(result) => result,
+            Err(_) => {
+                // the cancelled start dropped its child, this cleans up the rest
+                let _ = process.stop().await;
+                Err(format!(
+                    "start timed out after {}s, raise LOCALCHAIN_START_TIMEOUT_SECS for slow forks",
+                    deadline.as_secs()
+                ))
+            }
+        };
+        match result {
+            Ok(()) if process.chain_id_matches() => {
+                entry.status_tx.send_replace(ChainStatus::Running);
+                entry.logs.send(LogStream::Manager, "running".into());
                 Ok(())
             }
-            Err(e) => {
-                entry.config.status = ChainStatus::Error;
+            Ok(()) => {
+                // anvil stays up so the mismatch can be looked at, stop works from Error
+                let e = ManagerError::ChainIdMismatch {
+                    expected: process.chain_id,
+                    actual: process.actual_chain_id.load(Ordering::Relaxed),
+                };
+                entry.status_tx.send_replace(ChainStatus::Error);
+                entry.logs.send(LogStream::Manager, format!("error: {}", e));
                 Err(e)
             }
+            Err(e) => {
+                // a failure after the spawn may leave a container or tasks behind
+                let _ = process.stop().await;
+                entry.status_tx.send_replace(ChainStatus::Error);
+                entry.logs.send(LogStream::Manager, format!("error: {}", e));
+                Err(e.into())
+            }
         }
     }
 
-    async fn stop(&self, id: &u64) -> Result<(), String> {
-        let mut map = self.inner.lock().await;
-        let Some(entry) = map.get_mut(id) else {
-            return Err("not found".into());
-        };
+    async fn stop(&self, id: &u64) -> Result<(), ManagerError> {
+        // waits out a start in progress without holding the map
+        let entry = self.entry_handles(id).await?;
         let mut process = entry.process.lock().await;
         match process.stop().await {
             Ok(()) => {
-                entry.config.status = ChainStatus::Stopped;
-                let _ = entry.log_tx.send("[manager] stopped".into());
+                entry.status_tx.send_replace(ChainStatus::Stopped);
+                entry.logs.send(LogStream::Manager, "stopped".into());
                 Ok(())
             }
             Err(e) => {
-                entry.config.status = ChainStatus::Error;
-                Err(e)
+                entry.status_tx.send_replace(ChainStatus::Error);
+                entry.logs.send(LogStream::Manager, format!("error: {}", e));
+                Err(e.into())
             }
         }
     }
 
-    async fn restart(&self, id: &u64) -> Result<(), String> {
+    /// With `preserve` the chain's state is dumped before the stop and
+    /// loaded back after the start. If the dump fails it restarts from
+    /// genesis as usual.
+    async fn restart(&self, id: &u64, preserve: bool) -> Result<(), ManagerError> {
+        let process = self.process(id).await?;
+        let state = if preserve {
+            let process = process.lock().await;
+            process
+                .logs
+                .send(LogStream::Manager, "restarting, keeping state".into());
+            let state = match process.client() {
+                Ok(client) => client.dump_state().await,
+                Err(e) => Err(e),
+            };
+            match state {
+                Ok(state) => Some(state),
+                Err(e) => {
+                    process.logs.send(
+                        LogStream::Manager,
+                        format!("could not dump state, restarting without it: {}", e),
+                    );
+                    None
+                }
+            }
+        } else {
+            process
+                .lock()
+                .await
+                .logs
+                .send(LogStream::Manager, "restarting".into());
+            None
+        };
         self.stop(id).await?;
+        if state.is_some() {
+            process.lock().await.initial_state = state;
+        }
         self.start(id).await?;
         Ok(())
     }
 
-    async fn delete(&self, id: &u64) -> Result<(), String> {
-        let process = {
-            let mut map = self.inner.lock().await;
-            let Some(entry) = map.get_mut(id) else {
-                return Err("not found".into());
-            };
-            entry.process.clone()
+    /// Starts every chain that isn't running, one at a time so a failing
+    /// chain doesn't stop the rest
+    async fn start_all(&self) -> Vec<ChainResult> {
+        let mut results = vec![];
+        for cfg in self.list().await {
+            if cfg.status == ChainStatus::Running {
+                continue;
+            }
+            let result = self.start(&cfg.id).await;
+            results.push(ChainResult {
+                id: cfg.id,
+                name: cfg.name,
+                ok: result.is_ok(),
+                error: result.err().map(|e| e.to_string()),
+            });
+        }
+        results
+    }
+
+    /// Stops every chain that isn't already stopped
+    async fn stop_all(&self) -> Vec<ChainResult> {
+        let mut results = vec![];
+        for cfg in self.list().await {
+            if cfg.status == ChainStatus::Stopped {
+                continue;
+            }
+            let result = self.stop(&cfg.id).await;
+            results.push(ChainResult {
+                id: cfg.id,
+                name: cfg.name,
+                ok: result.is_ok(),
+                error: result.err().map(|e| e.to_string()),
+            });
+        }
+        results
+    }
+
+    /// Deletes every chain, each one stopped first like `delete` does
+    async fn delete_all(&self) -> Vec<ChainResult> {
+        let mut results = vec![];
+        for cfg in self.list().await {
+            let result = self.delete(&cfg.id).await;
+            results.push(ChainResult {
+                id: cfg.id,
+                name: cfg.name,
+                ok: result.is_ok(),
+                error: result.err().map(|e| e.to_string()),
+            });
+        }
+        results
+    }
+
+    /// Creates a copy of a running chain under the next free id and port.
+    /// The source's current state is loaded into the copy when it first
+    /// starts, restarts after that begin from genesis.
+    async fn fork_local(&self, id: &u64) -> Result<ChainConfig, ManagerError> {
+        let (client, _permit) = self.running_client(id).await?;
+        let source = self
+            .inner
+            .lock()
+            .await
+            .get(id)
+            .map(ChainEntry::config)
+            .ok_or(ManagerError::NotFound(*id))?;
+        let state = client.dump_state().await?;
+
+        let next = ChainConfig::next(&self.list().await);
+        let stored = self
+            .create(ChainConfig {
+                name: format!("{}-fork", source.name),
+                id: next.id,
+                port: next.port,
+                auto_start: false,
+                ..source
+            })
+            .await?;
+        let process = self.process(&stored.id).await?;
+        process.lock().await.initial_state = Some(state);
+        Ok(stored)
+    }
+
+    /// Only the display name changes, so this works on running chains too
+    async fn rename(&self, id: &u64, name: String) -> Result<ChainConfig, ManagerError> {
+        let mut map = self.inner.lock().await;
+        if map.values().any(|c| c.id != *id && c.config.name == name) {
+            return Err(ManagerError::AlreadyExists(format!(
+                "chain name {:?}",
+                name
+            )));
+        }
+        let Some(entry) = map.get_mut(id) else {
+            return Err(ManagerError::NotFound(*id));
+        };
+        ChainConfig {
+            name: name.clone(),
+            ..entry.config.clone()
+        }
+        .validate(self.allow_privileged_ports)?;
+        entry.config.name = name.clone();
+        let stored = entry.config();
+        let process = entry.process.clone();
+        drop(map);
+        process.lock().await.name = name;
+        self.persist().await;
+        self.notify(ChainEventKind::Updated, &stored);
+        Ok(stored)
+    }
+
+    /// Blank notes are stored as none
+    async fn set_notes(
+        &self,
+        id: &u64,
+        notes: Option<String>,
+    ) -> Result<ChainConfig, ManagerError> {
+        let mut map = self.inner.lock().await;
+        let Some(entry) = map.get_mut(id) else {
+            return Err(ManagerError::NotFound(*id));
+        };
+        entry.config.notes = notes.filter(|n| !n.trim().is_empty());
+        let stored = entry.config();
+        drop(map);
+        self.persist().await;
+        self.notify(ChainEventKind::Updated, &stored);
+        Ok(stored)
+    }
+
+    async fn set_pinned(&self, id: &u64, pinned: bool) -> Result<ChainConfig, ManagerError> {
+        let mut map = self.inner.lock().await;
+        let Some(entry) = map.get_mut(id) else {
+            return Err(ManagerError::NotFound(*id));
+        };
+        entry.config.pinned = pinned;
+        let stored = entry.config();
+        drop(map);
+        self.persist().await;
+        self.notify(ChainEventKind::Updated, &stored);
+        Ok(stored)
+    }
+
+    /// Changes the block time of a running chain in place, the new value is
+    /// stored so later starts use it too
+    async fn set_block_time(&self, id: &u64, secs: u64) -> Result<ChainConfig, ManagerError> {
+        let (process, _permit) = self.running_process(id).await?;
+        let Some(config) = self.inner.lock().await.get(id).map(|e| e.config.clone()) else {
+            return Err(ManagerError::NotFound(*id));
+        };
+        ChainConfig {
+            block_time: secs,
+            ..config
+        }
+        .validate(self.allow_privileged_ports)?;
+        process.lock().await.set_block_time(secs).await?;
+        let mut map = self.inner.lock().await;
+        let Some(entry) = map.get_mut(id) else {
+            return Err(ManagerError::NotFound(*id));
+        };
+        entry.config.block_time = secs;
+        let stored = entry.config();
+        drop(map);
+        self.persist().await;
+        self.notify(ChainEventKind::Updated, &stored);
+        Ok(stored)
+    }
+
+    /// Changes the block gas limit of a running chain in place, stored like
+    /// `set_block_time`
+    async fn set_block_gas_limit(&self, id: &u64, limit: u64) -> Result<ChainConfig, ManagerError> {
+        let (process, _permit) = self.running_process(id).await?;
+        let Some(config) = self.inner.lock().await.get(id).map(|e| e.config.clone()) else {
+            return Err(ManagerError::NotFound(*id));
+        };
+        ChainConfig {
+            block_gas_limit: Some(limit),
+            ..config
+        }
+        .validate(self.allow_privileged_ports)?;
+        process.lock().await.set_block_gas_limit(limit).await?;
+        let mut map = self.inner.lock().await;
+        let Some(entry) = map.get_mut(id) else {
+            return Err(ManagerError::NotFound(*id));
         };
+        entry.config.block_gas_limit = Some(limit);
+        let stored = entry.config();
+        drop(map);
+        self.persist().await;
+        self.notify(ChainEventKind::Updated, &stored);
+        Ok(stored)
+    }
+
+    /// Transactions recorded since the chain last started from genesis, also
+    /// available after it stopped
+    async fn recording(&self, id: &u64) -> Result<Recording, ManagerError> {
+        let map = self.inner.lock().await;
+        let Some(entry) = map.get(id) else {
+            return Err(ManagerError::NotFound(*id));
+        };
+        if !entry.config.record_transactions {
+            return Err(ManagerError::Backend(format!(
+                "chain {} does not record transactions",
+                id
+            )));
+        }
+        let process = entry.process.clone();
+        drop(map);
+        let transactions = process.lock().await.recording.lock().unwrap().clone();
+        Ok(Recording {
+            chain_id: *id,
+            transactions,
+        })
+    }
+
+    /// Sends a recording's transactions to the running chain `id`
+    async fn replay(&self, id: &u64, recording: Recording) -> Result<ReplayResult, ManagerError> {
+        let (client, _permit) = self.running_client(id).await?;
+        let result = client.replay(&recording.transactions).await?;
+        Ok(result)
+    }
+
+    /// Forwards a JSON-RPC call or batch to the running chain `id`. Calls the
+    /// policy refuses, or without a method, get an error response in their
+    /// place.
+    async fn rpc_proxy(
+        &self,
+        id: &u64,
+        body: serde_json::Value,
+        policy: &RpcPolicy,
+    ) -> Result<serde_json::Value, ManagerError> {
+        let (client, _permit) = self.running_client(id).await?;
+        let (calls, batch) = match body {
+            serde_json::Value::Array(calls) => (calls, true),
+            call => (vec![call], false),
+        };
+        if calls.is_empty() {
+            return Ok(rpc::error_response(
+                serde_json::Value::Null,
+                rpc::INVALID_REQUEST,
+                "empty batch",
+            ));
+        }
+        let mut responses = vec![];
+        for call in calls {
+            let call_id = call.get("id").cloned().unwrap_or_default();
+            let Some(method) = call.get("method").and_then(|m| m.as_str()) else {
+                responses.push(rpc::error_response(
+                    call_id,
+                    rpc::INVALID_REQUEST,
+                    "missing method",
+                ));
+                continue;
+            };
+            if !policy.allows(method) {
+                responses.push(rpc::error_response(
+                    call_id,
+                    rpc::METHOD_NOT_ALLOWED,
+                    format!("method {} is not allowed", method),
+                ));
+                continue;
+            }
+            // anvil wants an array even for methods without params
+            let params = call
+                .get("params")
+                .cloned()
+                .unwrap_or_else(|| serde_json::json!([]));
+            let params = serde_json::value::to_raw_value(&params).map_err(|e| e.to_string())?;
+            responses.push(client.forward(call_id, method.to_string(), &params).await);
+        }
+        Ok(match batch {
+            true => serde_json::Value::Array(responses),
+            false => responses.remove(0),
+        })
+    }
+
+    async fn delete(&self, id: &u64) -> Result<(), ManagerError> {
+        let process = self.process(id).await?;
         process.lock().await.stop().await?;
 
         let mut map = self.inner.lock().await;
-        map.remove(id);
+        let removed = map.remove(id).map(|entry| entry.config());
+        drop(map);
+        self.persist().await;
+        if let Some(cfg) = removed {
+            self.notify(ChainEventKind::Deleted, &cfg);
+        }
         Ok(())
     }
 
-    async fn subscribe_logs(&self, id: &u64) -> Result<broadcast::Receiver<String>, String> {
+    async fn subscribe_logs(
+        &self,
+        id: &u64,
+        tail: Option<usize>,
+    ) -> Result<(Vec<LogLine>, broadcast::Receiver<LogLine>), ManagerError> {
         let map = self.inner.lock().await;
         let Some(entry) = map.get(id) else {
-            return Err("not found".into());
+            return Err(ManagerError::NotFound(*id));
         };
-        Ok(entry.log_tx.subscribe())
+        Ok(entry.logs.subscribe(tail))
     }
 
-    async fn subscribe_blocks(&self, id: &u64) -> Result<broadcast::Receiver<Block>, String> {
+    async fn dump_logs(&self, id: &u64, prefix: &str) -> Result<String, ManagerError> {
         let map = self.inner.lock().await;
         let Some(entry) = map.get(id) else {
-            return Err("not found".into());
+            return Err(ManagerError::NotFound(*id));
         };
-        Ok(entry.block_tx.subscribe())
+        Ok(entry.logs.dump(prefix))
     }
 
-    async fn get_block(
+    async fn logs_since(
         &self,
-        chain_id: &u64,
-        block_number: u64,
-    ) -> Result<(Block, Vec<Transaction>), String> {
-        let process = {
+        id: &u64,
+        since: Option<u64>,
+        limit: Option<usize>,
+        prefix: &str,
+    ) -> Result<LogLinesResponse, ManagerError> {
+        let map = self.inner.lock().await;
+        let Some(entry) = map.get(id) else {
+            return Err(ManagerError::NotFound(*id));
+        };
+        Ok(entry.logs.since(since, limit, prefix))
+    }
+
+    async fn search_logs(
+        &self,
+        id: &u64,
+        query: &str,
+        limit: usize,
+        prefix: &str,
+    ) -> Result<LogSearchResponse, ManagerError> {
+        let map = self.inner.lock().await;
+        let Some(entry) = map.get(id) else {
+            return Err(ManagerError::NotFound(*id));
+        };
+        Ok(entry.logs.search(query, limit, prefix))
+    }
+
+    async fn subscribe_blocks(&self, id: &u64) -> Result<broadcast::Receiver<Block>, ManagerError> {
+        let map = self.inner.lock().await;
+        let Some(entry) = map.get(id) else {
+            return Err(ManagerError::NotFound(*id));
+        };
+        Ok(entry.block_tx.subscribe())
+    }
+
+    /// A port counts as free when no chain is configured on it and it can
+    /// be bound on the default host, which also catches other programs
+    async fn availability(&self, id: Option<u64>, port: Option<u16>) -> Availability {
+        let (id_taken, port_taken) = {
             let map = self.inner.lock().await;
-            let Some(entry) = map.get(chain_id) else {
-                return Err("chain not found".into());
-            };
-            entry.process.clone()
+            (
+                id.map(|id| map.contains_key(&id)),
+                port.map(|port| map.values().any(|c| c.config.port == port)),
+            )
+        };
+        let port_available = match (port, port_taken) {
+            (Some(port), Some(false)) if port != 0 => Some(
+                tokio::net::TcpListener::bind((DEFAULT_HOST, port))
+                    .await
+                    .is_ok(),
+            ),
+            (Some(_), _) => Some(false),
+            (None, _) => None,
+        };
+        Availability {
+            id_available: id_taken.map(|taken| !taken),
+            port_available,
+        }
+    }
+
+    async fn subscribe_status(
+        &self,
+        id: &u64,
+    ) -> Result<(watch::Receiver<ChainStatus>, Arc<LiveStats>), ManagerError> {
+        let map = self.inner.lock().await;
+        let Some(entry) = map.get(id) else {
+            return Err(ManagerError::NotFound(*id));
         };
+        Ok((entry.status_tx.subscribe(), entry.stats.clone()))
+    }
+
+    async fn resource_usage(&self, chain_id: &u64) -> Result<ResourceUsage, ManagerError> {
+        let process = self.process(chain_id).await?;
         let process = process.lock().await;
-        process.get_block_with_transactions(block_number).await
+        Ok(process.resource_usage().await?)
     }
 
-    async fn get_transaction(
+    async fn head(&self, chain_id: &u64) -> Result<u64, ManagerError> {
+        let (client, _permit) = self.running_client(chain_id).await?;
+        Ok(client.block_number().await?)
+    }
+
+    async fn trace_transaction(
         &self,
         chain_id: &u64,
         transaction_hash: String,
-    ) -> Result<Transaction, String> {
-        let process = {
-            let map = self.inner.lock().await;
-            let Some(entry) = map.get(chain_id) else {
-                return Err("chain not found".into());
-            };
-            entry.process.clone()
-        };
-        let process = process.lock().await;
-        process.get_transaction(transaction_hash).await
+    ) -> Result<serde_json::Value, ManagerError> {
+        let (client, _permit) = self.running_client(chain_id).await?;
+        Ok(client.trace_transaction(transaction_hash).await?)
     }
-}
 
-#[tokio::main]
-async fn main() {
-    let client_dist = std::env::var("CLIENT_DIST")
-        .map(PathBuf::from)
-        .unwrap_or_else(|_| {
-            // default to ../client/dist for trunk builds
-            let mut p = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-            p.pop();
-            p.push("client");
-            p.push("dist");
-            p
-        });
+    /// Runtime only, a restart resumes mining
+    async fn set_mining_paused(&self, id: &u64, paused: bool) -> Result<(), ManagerError> {
+        let (process, _permit) = self.running_process(id).await?;
+        let mut process = process.lock().await;
+        Ok(process.set_mining_paused(paused).await?)
+    }
 
-    let state = AppState {
-        client_dist: client_dist.clone(),
-        manager: Arc::new(ChainsManager::default()),
-    };
+    async fn mine(&self, id: &u64, blocks: u64) -> Result<(), ManagerError> {
+        let (client, _permit) = self.running_client(id).await?;
+        Ok(client.mine(blocks).await?)
+    }
 
-    // Serve static assets from /assets route only
-    let assets_dir = client_dist.join("assets");
-    let assets_service = ServeDir::new(&assets_dir);
+    async fn mine_until(
+        &self,
+        id: &u64,
+        condition: MineCondition,
+        max_blocks: u64,
+    ) -> Result<MineUntilResult, ManagerError> {
+        let (client, _permit) = self.running_client(id).await?;
+        Ok(client.mine_until(condition, max_blocks).await?)
+    }
 
-    let app = Router::new()
-        .route("/api/health", get(health))
-        .route("/api/chains", get(list_chains).post(create_chain))
-        .route("/api/chains/:id/start", post(start_chain))
-        .route("/api/chains/:id/stop", post(stop_chain))
-        .route("/api/chains/:id/restart", post(restart_chain))
-        .route("/api/chains/:id/delete", post(delete_chain))
-        .route("/api/chains/:id/logstream", get(log_stream))
+    async fn send_test_transaction(&self, id: &u64) -> Result<String, ManagerError> {
+        let (client, _permit) = self.running_client(id).await?;
+        Ok(client.send_test_transaction().await?)
+    }
+
+    async fn dev_private_keys(
+        &self,
+        chain_id: &u64,
+    ) -> Result<Vec<DevOnlyPrivateKey>, ManagerError> {
+        let process = self.process(chain_id).await?;
+        let process = process.lock().await;
+        Ok(process.dev_private_keys()?)
+    }
+
+    async fn get_blocks_range(
+        &self,
+        chain_id: &u64,
+        from: u64,
+        to: u64,
+    ) -> Result<Vec<Block>, ManagerError> {
+        let (client, _permit) = self.running_client(chain_id).await?;
+        Ok(client.get_blocks_range(from, to).await?)
+    }
+
+    async fn get_block(
+        &self,
+        chain_id: &u64,
+        block_number: u64,
+    ) -> Result<(Block, Vec<Transaction>), ManagerError> {
+        let (client, _permit) = self.running_client(chain_id).await?;
+        Ok(client.get_block_with_transactions(block_number).await?)
+    }
+
+    async fn genesis(&self, chain_id: &u64) -> Result<(Block, Vec<Transaction>), ManagerError> {
+        let (process, _permit) = self.running_process(chain_id).await?;
+        let mut process = process.lock().await;
+        Ok(process.genesis().await?)
+    }
+
+    async fn get_rpc_block(
+        &self,
+        chain_id: &u64,
+        block_number: u64,
+    ) -> Result<serde_json::Value, ManagerError> {
+        let (client, _permit) = self.running_client(chain_id).await?;
+        Ok(client.get_rpc_block(block_number).await?)
+    }
+
+    async fn get_rpc_transaction(
+        &self,
+        chain_id: &u64,
+        transaction_hash: String,
+    ) -> Result<serde_json::Value, ManagerError> {
+        let (client, _permit) = self.running_client(chain_id).await?;
+        Ok(client.get_rpc_transaction(transaction_hash).await?)
+    }
+
+    async fn get_transaction(
+        &self,
+        chain_id: &u64,
+        transaction_hash: String,
+    ) -> Result<TransactionResponse, ManagerError> {
+        let (client, _permit) = self.running_client(chain_id).await?;
+        let transaction = client.get_transaction(transaction_hash).await?;
+        let confirmations = match transaction.block_number {
+            // read after the transaction, so the head can't be behind its block
+            Some(block) => client.block_number().await?.saturating_sub(block) + 1,
+            None => 0,
+        };
+        Ok(TransactionResponse {
+            transaction,
+            confirmations,
+        })
+    }
+
+    async fn get_logs(
+        &self,
+        chain_id: &u64,
+        filter: LogFilter,
+    ) -> Result<LogsResponse, ManagerError> {
+        let (client, _permit) = self.running_client(chain_id).await?;
+        let mut logs = client.get_logs(&filter).await?;
+        logs.reverse();
+
+        let page = filter.page();
+        let page_size = filter.page_size();
+        let total = logs.len() as u64;
+        let logs = logs
+            .into_iter()
+            .skip((page * page_size) as usize)
+            .take(page_size as usize)
+            .collect();
+        Ok(LogsResponse {
+            logs,
+            total,
+            page,
+            page_size,
+        })
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let client_dist = std::env::var("CLIENT_DIST")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| {
+            // default to ../client/dist for trunk builds
+            let mut p = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+            p.pop();
+            p.push("client");
+            p.push("dist");
+            p
+        });
+
+    let anvil_version = AnvilVersion::detect().await;
+
+    let manager = Arc::new(ChainsManager {
+        state_file: std::env::var("LOCALCHAIN_STATE_FILE")
+            .ok()
+            .filter(|p| !p.is_empty())
+            .map(PathBuf::from),
+        max_chains: std::env::var("LOCALCHAIN_MAX_CHAINS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|max| *max > 0),
+        chain_concurrency: std::env::var("LOCALCHAIN_CHAIN_CONCURRENCY")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|n| *n > 0)
+            .map(|n: usize| n.min(Semaphore::MAX_PERMITS)),
+        log_dir: std::env::var("LOCALCHAIN_LOG_DIR")
+            .ok()
+            .filter(|p| !p.is_empty())
+            .map(PathBuf::from),
+        allow_privileged_ports: std::env::var("LOCALCHAIN_ALLOW_PRIVILEGED_PORTS")
+            .map(|v| !matches!(v.as_str(), "" | "0" | "false"))
+            .unwrap_or(false),
+        anvil_version,
+        ..Default::default()
+    });
+    let config_file = std::env::var("LOCALCHAIN_CONFIG")
+        .ok()
+        .filter(|p| !p.is_empty());
+    let declared = match &config_file {
+        Some(path) => read_chains_file(std::path::Path::new(path))
+            .await
+            .unwrap_or_else(|e| {
+                println!("ignoring config file {}: {}", path, e);
+                vec![]
+            }),
+        None => vec![],
+    };
+    let restored = manager.restore(declared).await;
+
+    let state = AppState {
+        client_built: check_client_dist(&client_dist),
+        client_dist: client_dist.clone(),
+        manager,
+        token: std::env::var("LOCALCHAIN_TOKEN")
+            .ok()
+            .filter(|t| !t.is_empty()),
+        read_only: std::env::var("LOCALCHAIN_READONLY")
+            .map(|v| !matches!(v.as_str(), "" | "0" | "false"))
+            .unwrap_or(false),
+        expose_keys: std::env::var("LOCALCHAIN_EXPOSE_KEYS")
+            .map(|v| !matches!(v.as_str(), "" | "0" | "false"))
+            .unwrap_or(false),
+        log_prefix: logs::prefix_template().into(),
+        shutdown: Arc::new(watch::Sender::new(false)),
+        anvil_version,
+        rpc_policy: Arc::new(RpcPolicy::from_env()),
+    };
+    let shutdown = state.shutdown.clone();
+    let manager = state.manager.clone();
+
+    // Serve static assets from /assets route only
+    let assets_dir = client_dist.join("assets");
+    let assets_service = ServeDir::new(&assets_dir);
+
+    let admin = Router::new()
+        .route("/api/admin/shutdown", post(admin_shutdown))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            auth::require_local_without_token,
+        ));
+
+    let mutating = Router::new()
+        .merge(admin)
+        .route("/api/chains", post(create_chain))
+        .route("/api/chains/import-all", post(import_chains))
+        .route("/api/chains/start-all", post(start_all_chains))
+        .route("/api/chains/stop-all", post(stop_all_chains))
+        .route("/api/chains/delete-all", post(delete_all_chains))
+        .route("/api/chains/:id/start", post(start_chain))
+        .route("/api/chains/:id/stop", post(stop_chain))
+        .route("/api/chains/:id/restart", post(restart_chain))
+        .route("/api/chains/:id/delete", post(delete_chain))
+        .route("/api/chains/:id/fork-local", post(fork_local_chain))
+        .route("/api/chains/:id/rename", post(rename_chain))
+        .route("/api/chains/:id/notes", post(set_chain_notes))
+        .route("/api/chains/:id/pin", post(pin_chain))
+        .route("/api/chains/:id/unpin", post(unpin_chain))
+        .route("/api/chains/:id/blocktime/:secs", post(set_block_time))
+        .route("/api/chains/:id/gaslimit/:limit", post(set_block_gas_limit))
+        .route("/api/chains/:id/testtx", post(send_test_transaction))
+        .route("/api/chains/:id/mine", post(mine_blocks))
+        .route("/api/chains/:id/mine-until", post(mine_until))
+        .route("/api/chains/:id/mining/:paused", post(set_mining_paused))
+        .route("/api/chains/:id/replay", post(replay_recording))
+        .route("/api/chains/:id/rpc", post(rpc_proxy))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            auth::reject_read_only,
+        ));
+
+    let api = Router::new()
+        .route("/api/chains", get(list_chains))
+        .route("/api/chains/export-all", get(export_chains))
+        .route("/api/chains/available", get(chain_availability))
+        .route("/api/chains/events", get(chain_events))
+        .merge(mutating)
+        .route("/api/chains/:id/logstream", get(log_stream))
+        .route("/api/chains/:id/logs", get(logs_since))
+        .route("/api/chains/:id/logs/download", get(download_logs))
+        .route("/api/chains/:id/logs/search", get(search_logs))
         .route("/api/chains/:id/blockstream", get(block_stream))
+        .route("/api/chains/:id/statusstream", get(status_stream))
+        .route("/api/chains/:id/usage", get(chain_usage))
+        .route("/api/chains/:id/recording", get(get_recording))
+        .route("/api/chains/:id/head", get(chain_head))
+        .route("/api/chains/:id/genesis", get(get_genesis))
+        .route("/api/chains/:id/keys", get(dev_private_keys))
+        .route("/api/chains/:id/blocks/range", get(get_blocks_range))
+        .route("/api/chains/:id/tx/:hash/trace", get(trace_transaction))
+        // every other route under /api/chains/:id/ wins as an exact match
+        .route("/api/chains/:id/:action", any(unknown_action))
         .route("/api/:chainid/blocks/:blocknumber", get(get_block))
         .route(
             "/api/:chainid/transactions/:transactionhash",
             get(get_transaction),
         )
+        .route("/api/:chainid/events", get(get_logs))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            auth::require_token,
+        ));
+
+    let addr: SocketAddr = ([127, 0, 0, 1], 3000).into();
+    print_startup_summary(&state, addr, restored, config_file.as_deref());
+
+    let app = Router::new()
+        .route("/api/health", get(health))
+        .route("/api/openapi.json", get(openapi_document))
+        .merge(api)
         .nest_service("/assets", assets_service)
         .fallback(serve_static_or_index)
         .with_state(state);
 
-    let addr: SocketAddr = ([127, 0, 0, 1], 3000).into();
-    println!("listening on http://{}", addr);
+    let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
+    let server = axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(shutdown_signal(shutdown.clone(), manager));
+    // a stream that missed the flag would hold a graceful shutdown forever,
+    // give connections a moment to finish and then exit regardless
+    let mut requested = shutdown.subscribe();
+    tokio::select! {
+        result = server => {
+            if let Err(err) = result {
+                println!("server error {}", err);
+            }
+        }
+        _ = async {
+            let _ = requested.wait_for(|v| *v).await;
+            tokio::time::sleep(SHUTDOWN_GRACE).await;
+        } => println!("open connections did not close within {:?}", SHUTDOWN_GRACE),
+    }
+}
+
+/// How long in-flight requests get to finish once shutdown is requested
+const SHUTDOWN_GRACE: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Resolves on Ctrl-C, SIGTERM or `/api/admin/shutdown`, after stopping
+/// every chain and flagging `shutdown` so event streams end
+async fn shutdown_signal(shutdown: Arc<watch::Sender<bool>>, manager: Arc<ChainsManager>) {
+    let mut requested = shutdown.subscribe();
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(_) => std::future::pending().await,
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+    tokio::select! {
+        _ = tokio::signal::ctrl_c() => {}
+        _ = terminate => {}
+        _ = requested.wait_for(|v| *v) => {}
+    }
+    println!("shutting down");
+    for result in manager.stop_all().await {
+        if let Some(error) = result.error {
+            println!("[{}] stop failed: {}", result.name, error);
+        }
+    }
+    shutdown.send_replace(true);
+}
+
+/// Ends an event stream once the server is shutting down, graceful shutdown
+/// waits for every open connection
+fn until_shutdown<S: Stream>(
+    stream: S,
+    shutdown: &watch::Sender<bool>,
+) -> impl Stream<Item = S::Item> {
+    let mut rx = shutdown.subscribe();
+    futures::StreamExt::take_until(stream, async move {
+        let _ = rx.wait_for(|v| *v).await;
+    })
+}
 
-    if let Err(err) = axum::serve(tokio::net::TcpListener::bind(addr).await.unwrap(), app).await {
-        println!("server error {}", err.to_string());
+async fn openapi_document() -> impl IntoResponse {
+    Json(openapi::document())
+}
+
+async fn health(State(state): State<AppState>) -> impl IntoResponse {
+    Json(Health {
+        status: "ok".into(),
+        read_only: state.read_only,
+        allow_privileged_ports: state.manager.allow_privileged_ports,
+        max_chains: state.manager.max_chains,
+        client_built: state.client_built,
+        anvil_version: state.anvil_version.map(|v| v.to_string()),
+    })
+}
+
+/// Chains listed under `chains` in a TOML file, or a JSON one for any other
+/// extension. Kept as plain fields so `restore` can tell which ones were set.
+async fn read_chains_file(
+    path: &std::path::Path,
+) -> Result<Vec<serde_json::Map<String, serde_json::Value>>, String> {
+    #[derive(Deserialize)]
+    struct ChainsFile {
+        #[serde(default)]
+        chains: Vec<serde_json::Map<String, serde_json::Value>>,
+    }
+    let text = tokio::fs::read_to_string(path)
+        .await
+        .map_err(|e| e.to_string())?;
+    let file: ChainsFile = match path.extension().and_then(|e| e.to_str()) {
+        Some("toml") => toml::from_str(&text).map_err(|e| e.to_string())?,
+        _ => serde_json::from_str(&text).map_err(|e| e.to_string())?,
+    };
+    Ok(file.chains)
+}
+
+/// One line per setting that matters when reporting a problem, printed
+/// once everything is set up
+fn print_startup_summary(
+    state: &AppState,
+    addr: SocketAddr,
+    restored: usize,
+    config_file: Option<&str>,
+) {
+    let on_off = |on: bool| if on { "on" } else { "off" };
+    let sources: Vec<String> = [
+        state
+            .manager
+            .state_file
+            .as_ref()
+            .map(|p| format!("state file {}", p.display())),
+        config_file.map(|p| format!("config file {}", p)),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    println!("localchain {}", env!("CARGO_PKG_VERSION"));
+    println!("  listening:   http://{}", addr);
+    println!(
+        "  client dist: {} ({})",
+        state.client_dist.display(),
+        if state.client_built {
+            "built"
+        } else {
+            "missing"
+        }
+    );
+    match state.anvil_version {
+        Some(version) => println!("  anvil:       {}", version),
+        None => {
+            println!("  anvil:       unknown, could not run `anvil --version`, is anvil on PATH?")
+        }
+    }
+    if sources.is_empty() {
+        println!("  chains:      not persisted, LOCALCHAIN_STATE_FILE is unset");
+    } else {
+        println!(
+            "  chains:      {} restored from {}",
+            restored,
+            sources.join(" and ")
+        );
     }
+    println!("  auth token:  {}", on_off(state.token.is_some()));
+    println!("  read-only:   {}", on_off(state.read_only));
 }
 
-async fn health() -> impl IntoResponse {
-    (StatusCode::OK, "ok")
+/// Warns about a missing client build, the usual cause of a blank page after
+/// a fresh clone, and returns whether `index.html` is there
+fn check_client_dist(dist: &std::path::Path) -> bool {
+    let hint = "run `trunk build` in client/ or point CLIENT_DIST at a build";
+    if !dist.is_dir() {
+        println!(
+            "warning: client dist {} not found, {}",
+            dist.display(),
+            hint
+        );
+        return false;
+    }
+    let index = dist.join("index.html");
+    if !index.is_file() {
+        println!("warning: {} not found, {}", index.display(), hint);
+        return false;
+    }
+    let assets = dist.join("assets");
+    if !assets.is_dir() {
+        println!(
+            "warning: {} not found, /assets requests will 404",
+            assets.display()
+        );
+    }
+    true
 }
 
 async fn serve_static_or_index(
@@ -273,67 +1481,378 @@ async fn serve_static_or_index(
     }
 }
 
-async fn list_chains(State(state): State<AppState>) -> impl IntoResponse {
-    let list = state.manager.list().await;
-    Json(list)
+#[derive(Deserialize, JsonSchema)]
+struct ListQuery {
+    /// Wrap the list in a `ChainsSummary` with counts per status
+    #[serde(default)]
+    summary: bool,
+}
+
+async fn list_chains(
+    State(state): State<AppState>,
+    Query(query): Query<ListQuery>,
+) -> impl IntoResponse {
+    let list: Vec<ChainConfig> = state
+        .manager
+        .list()
+        .await
+        .iter()
+        .map(ChainConfig::redacted)
+        .collect();
+    if query.summary {
+        Json(ChainsSummary::new(list)).into_response()
+    } else {
+        Json(list).into_response()
+    }
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct ExportQuery {
+    /// Keep fork URL API keys so the file can be re-imported as is
+    #[serde(default)]
+    include_secrets: bool,
+}
+
+async fn export_chains(
+    State(state): State<AppState>,
+    Query(query): Query<ExportQuery>,
+) -> impl IntoResponse {
+    let mut list = state.manager.list().await;
+    if !query.include_secrets {
+        list = list.iter().map(ChainConfig::redacted).collect();
+    }
+    (
+        [(
+            header::CONTENT_DISPOSITION,
+            "attachment; filename=\"localchain-chains.json\"",
+        )],
+        Json(list),
+    )
+}
+
+async fn import_chains(
+    State(state): State<AppState>,
+    Json(req): Json<Vec<ChainConfig>>,
+) -> impl IntoResponse {
+    Json(state.manager.import(req).await)
+}
+
+/// `POST /api/chains/:id/<action>` routes, listed when an unknown one is hit
+const CHAIN_ACTIONS: &[&str] = &[
+    "start",
+    "stop",
+    "restart",
+    "delete",
+    "fork-local",
+    "rename",
+    "notes",
+    "pin",
+    "unpin",
+    "blocktime/:secs",
+    "gaslimit/:limit",
+    "testtx",
+    "mine",
+    "mine-until",
+    "mining/:paused",
+    "replay",
+    "rpc",
+];
+
+/// Answers JSON instead of falling through to the SPA's index.html
+async fn unknown_action(Path((_id, action)): Path<(u64, String)>) -> impl IntoResponse {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(serde_json::json!({
+            "error": format!("unknown action {:?}", action),
+            "valid_actions": CHAIN_ACTIONS,
+        })),
+    )
 }
 
 async fn create_chain(
     State(state): State<AppState>,
     Json(req): Json<ChainConfig>,
 ) -> impl IntoResponse {
-    match state.manager.create(req.clone()).await {
-        Ok(_) => (StatusCode::OK, Json(req)).into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+    match state.manager.create(req).await {
+        Ok(cfg) => (StatusCode::OK, Json(cfg.redacted())).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct RenameRequest {
+    name: String,
+}
+
+async fn rename_chain(
+    State(state): State<AppState>,
+    Path(id): Path<u64>,
+    Json(req): Json<RenameRequest>,
+) -> impl IntoResponse {
+    state
+        .manager
+        .rename(&id, req.name)
+        .await
+        .map(|cfg| (StatusCode::OK, Json(cfg.redacted())))
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct NotesRequest {
+    notes: Option<String>,
+}
+
+async fn set_chain_notes(
+    State(state): State<AppState>,
+    Path(id): Path<u64>,
+    Json(req): Json<NotesRequest>,
+) -> impl IntoResponse {
+    state
+        .manager
+        .set_notes(&id, req.notes)
+        .await
+        .map(|cfg| (StatusCode::OK, Json(cfg.redacted())))
+}
+
+async fn pin_chain(State(state): State<AppState>, Path(id): Path<u64>) -> impl IntoResponse {
+    state
+        .manager
+        .set_pinned(&id, true)
+        .await
+        .map(|cfg| (StatusCode::OK, Json(cfg.redacted())))
+}
+
+async fn unpin_chain(State(state): State<AppState>, Path(id): Path<u64>) -> impl IntoResponse {
+    state
+        .manager
+        .set_pinned(&id, false)
+        .await
+        .map(|cfg| (StatusCode::OK, Json(cfg.redacted())))
+}
+
+async fn set_block_time(
+    State(state): State<AppState>,
+    Path((id, secs)): Path<(u64, u64)>,
+) -> impl IntoResponse {
+    state
+        .manager
+        .set_block_time(&id, secs)
+        .await
+        .map(|cfg| (StatusCode::OK, Json(cfg.redacted())))
+}
+
+async fn set_block_gas_limit(
+    State(state): State<AppState>,
+    Path((id, limit)): Path<(u64, u64)>,
+) -> impl IntoResponse {
+    state
+        .manager
+        .set_block_gas_limit(&id, limit)
+        .await
+        .map(|cfg| (StatusCode::OK, Json(cfg.redacted())))
+}
+
+async fn get_recording(State(state): State<AppState>, Path(id): Path<u64>) -> impl IntoResponse {
+    state.manager.recording(&id).await.map(Json)
+}
+
+async fn replay_recording(
+    State(state): State<AppState>,
+    Path(id): Path<u64>,
+    Json(req): Json<Recording>,
+) -> impl IntoResponse {
+    state.manager.replay(&id, req).await.map(Json)
+}
+
+async fn rpc_proxy(
+    State(state): State<AppState>,
+    Path(id): Path<u64>,
+    Json(body): Json<serde_json::Value>,
+) -> impl IntoResponse {
+    state
+        .manager
+        .rpc_proxy(&id, body, &state.rpc_policy)
+        .await
+        .map(Json)
+}
+
+async fn fork_local_chain(State(state): State<AppState>, Path(id): Path<u64>) -> impl IntoResponse {
+    match state.manager.fork_local(&id).await {
+        Ok(cfg) => (StatusCode::OK, Json(cfg.redacted())).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
 async fn start_chain(State(state): State<AppState>, Path(id): Path<u64>) -> impl IntoResponse {
     match state.manager.start(&id).await {
         Ok(()) => (StatusCode::OK).into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
 async fn stop_chain(State(state): State<AppState>, Path(id): Path<u64>) -> impl IntoResponse {
     match state.manager.stop(&id).await {
         Ok(()) => (StatusCode::OK).into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
-async fn restart_chain(State(state): State<AppState>, Path(id): Path<u64>) -> impl IntoResponse {
-    match state.manager.restart(&id).await {
+#[derive(Deserialize, JsonSchema)]
+struct RestartQuery {
+    /// Carry accounts, storage and blocks over to the new run
+    #[serde(default)]
+    preserve: bool,
+}
+
+async fn restart_chain(
+    State(state): State<AppState>,
+    Path(id): Path<u64>,
+    Query(query): Query<RestartQuery>,
+) -> impl IntoResponse {
+    match state.manager.restart(&id, query.preserve).await {
         Ok(()) => (StatusCode::OK).into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
+/// Always 200, failures are reported per chain
+async fn start_all_chains(State(state): State<AppState>) -> impl IntoResponse {
+    Json(state.manager.start_all().await)
+}
+
+/// Always 200, failures are reported per chain
+async fn stop_all_chains(State(state): State<AppState>) -> impl IntoResponse {
+    Json(state.manager.stop_all().await)
+}
+
+async fn delete_all_chains(State(state): State<AppState>) -> impl IntoResponse {
+    Json(state.manager.delete_all().await)
+}
+
+/// Stops every chain like `stop-all`, then shuts the server down once the
+/// response is sent
+async fn admin_shutdown(State(state): State<AppState>) -> impl IntoResponse {
+    let results = state.manager.stop_all().await;
+    state.shutdown.send_replace(true);
+    Json(results)
+}
+
 async fn delete_chain(State(state): State<AppState>, Path(id): Path<u64>) -> impl IntoResponse {
     match state.manager.delete(&id).await {
         Ok(()) => (StatusCode::OK).into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
+#[derive(Deserialize, JsonSchema)]
+struct LogStreamQuery {
+    /// Replay only the last `tail` buffered lines before going live
+    tail: Option<usize>,
+    /// `json` sends each line as a `LogLine` object instead of plain text
+    format: Option<String>,
+}
+
 async fn log_stream(
     State(state): State<AppState>,
     Path(id): Path<u64>,
+    Query(query): Query<LogStreamQuery>,
 ) -> Sse<impl Stream<Item = Result<sse::Event, Infallible>>> {
     let stream: Pin<Box<dyn Stream<Item = Result<sse::Event, Infallible>> + Send>> =
-        match state.manager.subscribe_logs(&id).await {
-            Ok(rx) => {
-                let s = BroadcastStream::new(rx).map(|msg| match msg {
-                    Ok(line) => Ok(sse::Event::default().data(line)),
+        match state.manager.subscribe_logs(&id, query.tail).await {
+            Ok((backlog, rx)) => {
+                let json = query.format.as_deref() == Some("json");
+                let prefix = state.log_prefix.clone();
+                let render = move |line: LogLine| {
+                    let data = if json {
+                        line.to_json()
+                    } else {
+                        line.to_plain(&prefix)
+                    };
+                    Ok(sse::Event::default().data(data))
+                };
+                let backlog = tokio_stream::iter(backlog).map(render.clone());
+                let live = BroadcastStream::new(rx).map(move |msg| match msg {
+                    Ok(line) => render(line),
                     Err(_) => Ok(sse::Event::default().event("ping").data("")),
                 });
-                Box::pin(s)
+                Box::pin(backlog.chain(live))
             }
             Err(_) => Box::pin(tokio_stream::once(Ok(sse::Event::default()
                 .event("error")
                 .data("not found")))),
         };
-    Sse::new(stream).keep_alive(sse::KeepAlive::new())
+    Sse::new(until_shutdown(stream, &state.shutdown)).keep_alive(sse::KeepAlive::new())
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct LogsSinceQuery {
+    /// Index of the first line wanted, `next_index` of the previous fetch.
+    /// The oldest buffered line when missing.
+    since: Option<u64>,
+    /// At most this many lines, everything from `since` on when missing
+    limit: Option<usize>,
+}
+
+/// Reads the log incrementally without holding a stream open, for scripts
+/// and clients whose event streams are blocked
+async fn logs_since(
+    State(state): State<AppState>,
+    Path(id): Path<u64>,
+    Query(query): Query<LogsSinceQuery>,
+) -> Response {
+    state
+        .manager
+        .logs_since(&id, query.since, query.limit, &state.log_prefix)
+        .await
+        .map(|lines| (StatusCode::OK, Json(lines)))
+        .into_response()
+}
+
+async fn download_logs(State(state): State<AppState>, Path(id): Path<u64>) -> impl IntoResponse {
+    state
+        .manager
+        .dump_logs(&id, &state.log_prefix)
+        .await
+        .map(|text| {
+            (
+                [
+                    (
+                        header::CONTENT_TYPE,
+                        "text/plain; charset=utf-8".to_string(),
+                    ),
+                    (
+                        header::CONTENT_DISPOSITION,
+                        format!("attachment; filename=\"chain-{}.log\"", id),
+                    ),
+                ],
+                text,
+            )
+        })
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct LogSearchQuery {
+    q: String,
+    limit: Option<usize>,
+}
+
+async fn search_logs(
+    State(state): State<AppState>,
+    Path(id): Path<u64>,
+    Query(query): Query<LogSearchQuery>,
+) -> Response {
+    if query.q.is_empty() {
+        return (StatusCode::BAD_REQUEST, "q is required").into_response();
+    }
+    let limit = query
+        .limit
+        .unwrap_or(LogSearchResponse::DEFAULT_LIMIT)
+        .clamp(1, LogSearchResponse::MAX_LIMIT);
+    state
+        .manager
+        .search_logs(&id, &query.q, limit, &state.log_prefix)
+        .await
+        .map(|found| (StatusCode::OK, Json(found)))
+        .into_response()
 }
 
 async fn block_stream(
@@ -353,13 +1872,253 @@ async fn block_stream(
                 .event("error")
                 .data("not found")))),
         };
-    Sse::new(stream).keep_alive(sse::KeepAlive::new())
+    Sse::new(until_shutdown(stream, &state.shutdown)).keep_alive(sse::KeepAlive::new())
+}
+
+/// Every chain created, changed or deleted from now on
+async fn chain_events(
+    State(state): State<AppState>,
+) -> Sse<impl Stream<Item = Result<sse::Event, Infallible>>> {
+    let rx = state.manager.events.0.subscribe();
+    let stream = BroadcastStream::new(rx).map(|msg| match msg {
+        Ok(event) => {
+            Ok(sse::Event::default().data(serde_json::to_string(&event).unwrap_or_default()))
+        }
+        Err(_) => Ok(sse::Event::default().event("ping").data("")),
+    });
+    Sse::new(until_shutdown(stream, &state.shutdown)).keep_alive(sse::KeepAlive::new())
+}
+
+/// How often the status stream repeats the current stats without a change
+const STATUS_STATS_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+async fn status_stream(
+    State(state): State<AppState>,
+    Path(id): Path<u64>,
+) -> Sse<impl Stream<Item = Result<sse::Event, Infallible>>> {
+    let stream: Pin<Box<dyn Stream<Item = Result<sse::Event, Infallible>> + Send>> =
+        match state.manager.subscribe_status(&id).await {
+            Ok((rx, stats)) => {
+                // the first event goes out immediately, then on every status
+                // change or tick, whichever comes first
+                let s = futures::stream::unfold(
+                    (rx, stats, true),
+                    |(mut rx, stats, first)| async move {
+                        if !first {
+                            tokio::select! {
+                                changed = rx.changed() => changed.ok()?,
+                                _ = tokio::time::sleep(STATUS_STATS_INTERVAL) => {}
+                            }
+                        }
+                        let head = stats.head.load(Ordering::Relaxed);
+                        let event = ChainStatusEvent {
+                            status: *rx.borrow_and_update(),
+                            latest_block: Some(head).filter(|h| *h != 0),
+                            transactions: stats.transactions.load(Ordering::Relaxed),
+                            rpc_connected: stats.rpc_connected.load(Ordering::Relaxed),
+                            mining_paused: stats.mining_paused.load(Ordering::Relaxed),
+                            streaming_available: stats.streaming_available.load(Ordering::Relaxed),
+                        };
+                        let data = serde_json::to_string(&event).unwrap_or_default();
+                        Some((Ok(sse::Event::default().data(data)), (rx, stats, false)))
+                    },
+                );
+                Box::pin(s)
+            }
+            Err(_) => Box::pin(tokio_stream::once(Ok(sse::Event::default()
+                .event("error")
+                .data("not found")))),
+        };
+    Sse::new(until_shutdown(stream, &state.shutdown)).keep_alive(sse::KeepAlive::new())
+}
+
+async fn chain_usage(State(state): State<AppState>, Path(id): Path<u64>) -> impl IntoResponse {
+    state
+        .manager
+        .resource_usage(&id)
+        .await
+        .map(|usage| (StatusCode::OK, Json(usage)))
+}
+
+async fn chain_head(State(state): State<AppState>, Path(id): Path<u64>) -> impl IntoResponse {
+    state
+        .manager
+        .head(&id)
+        .await
+        .map(|number| (StatusCode::OK, Json(number)))
+}
+
+async fn trace_transaction(
+    State(state): State<AppState>,
+    Path((chain_id, transaction_hash)): Path<(u64, String)>,
+) -> impl IntoResponse {
+    state
+        .manager
+        .trace_transaction(&chain_id, transaction_hash)
+        .await
+        .map(|trace| (StatusCode::OK, Json(trace)))
+}
+
+/// Most blocks one `/mine` call may ask for
+const MAX_MINE_BLOCKS: u64 = 1000;
+
+#[derive(Deserialize, JsonSchema)]
+struct MineQuery {
+    /// 1 when unset
+    blocks: Option<u64>,
+}
+
+async fn mine_blocks(
+    State(state): State<AppState>,
+    Path(id): Path<u64>,
+    Query(query): Query<MineQuery>,
+) -> Response {
+    let blocks = query.blocks.unwrap_or(1);
+    if !(1..=MAX_MINE_BLOCKS).contains(&blocks) {
+        return (
+            StatusCode::BAD_REQUEST,
+            format!("blocks must be between 1 and {}", MAX_MINE_BLOCKS),
+        )
+            .into_response();
+    }
+    state.manager.mine(&id, blocks).await.into_response()
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct MineUntilRequest {
+    /// Mine until the head is at least this block
+    target_block: Option<u64>,
+    /// Mine until this transaction is included
+    tx_hash: Option<String>,
+    /// Give up after this many blocks, at most and by default 1000
+    max_blocks: Option<u64>,
+}
+
+async fn mine_until(
+    State(state): State<AppState>,
+    Path(id): Path<u64>,
+    Json(body): Json<MineUntilRequest>,
+) -> Response {
+    let condition = match (body.target_block, body.tx_hash) {
+        (Some(target), None) => MineCondition::Block(target),
+        (None, Some(hash)) => match hash.parse() {
+            Ok(hash) => MineCondition::Receipt(hash),
+            Err(e) => {
+                return (StatusCode::BAD_REQUEST, format!("invalid tx_hash: {}", e)).into_response()
+            }
+        },
+        _ => {
+            return (
+                StatusCode::BAD_REQUEST,
+                "exactly one of target_block and tx_hash is required",
+            )
+                .into_response()
+        }
+    };
+    let max_blocks = body.max_blocks.unwrap_or(MAX_MINE_BLOCKS);
+    if !(1..=MAX_MINE_BLOCKS).contains(&max_blocks) {
+        return (
+            StatusCode::BAD_REQUEST,
+            format!("max_blocks must be between 1 and {}", MAX_MINE_BLOCKS),
+        )
+            .into_response();
+    }
+    state
+        .manager
+        .mine_until(&id, condition, max_blocks)
+        .await
+        .map(Json)
+        .into_response()
+}
+
+async fn set_mining_paused(
+    State(state): State<AppState>,
+    Path((id, paused)): Path<(u64, bool)>,
+) -> impl IntoResponse {
+    state.manager.set_mining_paused(&id, paused).await
+}
+
+async fn send_test_transaction(
+    State(state): State<AppState>,
+    Path(id): Path<u64>,
+) -> impl IntoResponse {
+    state
+        .manager
+        .send_test_transaction(&id)
+        .await
+        .map(|hash| (StatusCode::OK, Json(hash)))
+}
+
+async fn dev_private_keys(State(state): State<AppState>, Path(id): Path<u64>) -> Response {
+    if !state.expose_keys {
+        return (
+            StatusCode::FORBIDDEN,
+            "private keys are not exposed, set LOCALCHAIN_EXPOSE_KEYS to enable",
+        )
+            .into_response();
+    }
+    match state.manager.dev_private_keys(&id).await {
+        Ok(keys) => (StatusCode::OK, Json(keys)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct BlockRangeQuery {
+    from: u64,
+    to: u64,
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct AvailabilityQuery {
+    id: Option<u64>,
+    port: Option<u16>,
+}
+
+async fn chain_availability(
+    State(state): State<AppState>,
+    Query(query): Query<AvailabilityQuery>,
+) -> impl IntoResponse {
+    Json(state.manager.availability(query.id, query.port).await)
+}
+
+async fn get_blocks_range(
+    State(state): State<AppState>,
+    Path(id): Path<u64>,
+    Query(query): Query<BlockRangeQuery>,
+) -> impl IntoResponse {
+    state
+        .manager
+        .get_blocks_range(&id, query.from, query.to)
+        .await
+        .map(|blocks| (StatusCode::OK, Json(blocks)))
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct FormatQuery {
+    /// `rpc` returns the node's JSON-RPC object instead of the trimmed type
+    format: Option<String>,
+}
+
+impl FormatQuery {
+    fn rpc(&self) -> bool {
+        self.format.as_deref() == Some("rpc")
+    }
 }
 
 async fn get_block(
     State(state): State<AppState>,
     Path((chain_id, block_number)): Path<(u64, u64)>,
-) -> impl IntoResponse {
+    Query(query): Query<FormatQuery>,
+) -> Response {
+    if query.rpc() {
+        return state
+            .manager
+            .get_rpc_block(&chain_id, block_number)
+            .await
+            .map(|block| (StatusCode::OK, Json(block)))
+            .into_response();
+    }
     state
         .manager
         .get_block(&chain_id, block_number)
@@ -368,22 +2127,174 @@ async fn get_block(
             (
                 StatusCode::OK,
                 Json(BlockResponse {
-                    block: block,
-                    transactions: transactions,
+                    block,
+                    transactions,
                 }),
             )
         })
-        .map_err(|e| (StatusCode::BAD_REQUEST, e))
+        .into_response()
+}
+
+async fn get_genesis(State(state): State<AppState>, Path(id): Path<u64>) -> impl IntoResponse {
+    state
+        .manager
+        .genesis(&id)
+        .await
+        .map(|(block, transactions)| {
+            Json(BlockResponse {
+                block,
+                transactions,
+            })
+        })
 }
 
 async fn get_transaction(
     State(state): State<AppState>,
     Path((chain_id, transaction_hash)): Path<(u64, String)>,
-) -> impl IntoResponse {
+    Query(query): Query<FormatQuery>,
+) -> Response {
+    if query.rpc() {
+        return state
+            .manager
+            .get_rpc_transaction(&chain_id, transaction_hash)
+            .await
+            .map(|tx| (StatusCode::OK, Json(tx)))
+            .into_response();
+    }
     state
         .manager
         .get_transaction(&chain_id, transaction_hash)
         .await
-        .map(|t| (StatusCode::OK, Json(TransactionResponse { transaction: t })))
-        .map_err(|e| (StatusCode::BAD_REQUEST, e))
+        .map(|t| (StatusCode::OK, Json(t)))
+        .into_response()
+}
+
+async fn get_logs(
+    State(state): State<AppState>,
+    Path(chain_id): Path<u64>,
+    Query(filter): Query<LogFilter>,
+) -> impl IntoResponse {
+    state
+        .manager
+        .get_logs(&chain_id, filter)
+        .await
+        .map(|logs| (StatusCode::OK, Json(logs)))
+}
+
+/// Runs against a real anvil, skipped when none is on the PATH
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn anvil_available() -> bool {
+        std::process::Command::new("anvil")
+            .arg("--version")
+            .output()
+            .is_ok_and(|out| out.status.success())
+    }
+
+    fn free_port() -> u16 {
+        std::net::TcpListener::bind("127.0.0.1:0")
+            .and_then(|l| l.local_addr())
+            .map(|addr| addr.port())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn start_stream_stop_lifecycle() {
+        if !anvil_available() {
+            println!("anvil not found, skipping");
+            return;
+        }
+        let manager = ChainsManager::default();
+        let cfg = ChainConfig {
+            id: 31_337_001,
+            name: "lifecycle".into(),
+            port: free_port(),
+            block_time: 1,
+            ..Default::default()
+        };
+        let id = manager.create(cfg).await.unwrap().id;
+        let mut blocks = manager.subscribe_blocks(&id).await.unwrap();
+
+        manager.start(&id).await.unwrap();
+        let process = manager.inner.lock().await[&id].process.clone();
+        let pid = process.lock().await.pid().expect("anvil is running");
+
+        let block = tokio::time::timeout(Duration::from_secs(10), blocks.recv())
+            .await
+            .expect("no block within 10s")
+            .unwrap();
+        assert!(block.number > 0);
+
+        let (genesis, transactions) = manager.get_block(&id, 0).await.unwrap();
+        assert_eq!(genesis.number, 0);
+        assert!(transactions.is_empty());
+
+        manager.stop(&id).await.unwrap();
+        assert!(process.lock().await.pid().is_none());
+        let alive = std::process::Command::new("kill")
+            .args(["-0", &pid.to_string()])
+            .status()
+            .is_ok_and(|s| s.success());
+        assert!(!alive, "anvil {} still running after stop", pid);
+    }
+
+    #[tokio::test]
+    async fn stopped_is_the_last_log_line() {
+        if !anvil_available() {
+            println!("anvil not found, skipping");
+            return;
+        }
+        let manager = ChainsManager::default();
+        let cfg = ChainConfig {
+            id: 31_337_002,
+            name: "stop-log".into(),
+            port: free_port(),
+            block_time: 1,
+            ..Default::default()
+        };
+        let id = manager.create(cfg).await.unwrap().id;
+        manager.start(&id).await.unwrap();
+        manager.stop(&id).await.unwrap();
+
+        let logs = manager.inner.lock().await[&id].logs.clone();
+        let (lines, _) = logs.subscribe(Some(1));
+        let last = lines.last().expect("the chain logged something");
+        assert_eq!(last.stream, LogStream::Manager);
+        assert_eq!(last.text, "stopped");
+    }
+
+    #[tokio::test]
+    async fn blocks_range_on_a_fresh_chain() {
+        if !anvil_available() {
+            println!("anvil not found, skipping");
+            return;
+        }
+        let manager = ChainsManager::default();
+        let cfg = ChainConfig {
+            id: 31_337_003,
+            name: "range".into(),
+            port: free_port(),
+            block_time: 0,
+            premine_blocks: Some(5),
+            ..Default::default()
+        };
+        let id = manager.create(cfg).await.unwrap().id;
+        manager.start(&id).await.unwrap();
+
+        let blocks = manager.get_blocks_range(&id, 1, 3).await.unwrap();
+        let numbers: Vec<u64> = blocks.iter().map(|b| b.number).collect();
+        assert_eq!(numbers, [1, 2, 3]);
+        // numbers past the head are skipped
+        assert_eq!(manager.get_blocks_range(&id, 4, 9).await.unwrap().len(), 2);
+        assert!(manager.get_blocks_range(&id, 3, 1).await.is_err());
+        assert!(manager
+            .get_blocks_range(&id, 0, crate::anvil::process::MAX_BLOCK_RANGE)
+            .await
+            .is_err());
+
+        manager.stop(&id).await.unwrap();
+    }
 }
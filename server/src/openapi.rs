@@ -0,0 +1,540 @@
+//! OpenAPI 3.0 description of the `/api` routes, served at `/api/openapi.json`.
+//! The route table is kept here by hand, every schema in it is derived from
+//! the shared types and the handlers' query and body structs.
+
+use crate::{
+    AvailabilityQuery, BlockRangeQuery, ExportQuery, FormatQuery, ListQuery, LogSearchQuery,
+    LogStreamQuery, LogsSinceQuery, MineQuery, MineUntilRequest, NotesRequest, RenameRequest,
+    RestartQuery,
+};
+use schemars::generate::{SchemaGenerator, SchemaSettings};
+use schemars::JsonSchema;
+use serde_json::{json, Map, Value};
+use shared::types::{
+    availability::Availability, block::Block, block_response::BlockResponse,
+    chain_config::ChainConfig, chain_event::ChainEvent, chain_result::ChainResult,
+    chain_status_event::ChainStatusEvent, dev_only_private_key::DevOnlyPrivateKey, health::Health,
+    log_filter::LogFilter, log_lines::LogLinesResponse, log_search::LogSearchResponse,
+    logs_response::LogsResponse, mine_until_result::MineUntilResult, recording::Recording,
+    replay_result::ReplayResult, resource_usage::ResourceUsage,
+    transaction_response::TransactionResponse,
+};
+
+/// What a route answers with on success
+enum Reply {
+    Empty,
+    Json(Value),
+    Text,
+    /// Server-sent events, each `data` holding the given schema or a plain line
+    Events(Option<Value>),
+}
+
+struct Spec {
+    generator: SchemaGenerator,
+    paths: Map<String, Value>,
+}
+
+impl Spec {
+    fn schema<T: JsonSchema>(&mut self) -> Value {
+        self.generator.subschema_for::<T>().to_value()
+    }
+
+    /// One query parameter per field of `T`
+    fn query<T: JsonSchema>(&mut self) -> Vec<Value> {
+        let root = self.generator.root_schema_for::<T>().to_value();
+        let required: Vec<&str> = root["required"]
+            .as_array()
+            .map(|r| r.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+        let Some(properties) = root["properties"].as_object() else {
+            return vec![];
+        };
+        properties
+            .iter()
+            .map(|(name, schema)| {
+                let mut schema = schema.clone();
+                let description = schema.as_object_mut().and_then(|s| s.remove("description"));
+                json!({
+                    "name": name,
+                    "in": "query",
+                    "required": required.contains(&name.as_str()),
+                    "description": description.unwrap_or_default(),
+                    "schema": schema,
+                })
+            })
+            .collect()
+    }
+
+    fn route(
+        &mut self,
+        method: &str,
+        path: &str,
+        summary: &str,
+        query: Vec<Value>,
+        body: Option<Value>,
+        reply: Reply,
+    ) {
+        let mut parameters = path_params(path);
+        parameters.extend(query);
+        let success = match reply {
+            Reply::Empty => json!({ "description": "OK" }),
+            Reply::Json(schema) => json!({
+                "description": "OK",
+                "content": { "application/json": { "schema": schema } },
+            }),
+            Reply::Text => json!({
+                "description": "OK",
+                "content": { "text/plain": { "schema": { "type": "string" } } },
+            }),
+            Reply::Events(schema) => json!({
+                "description": "Event stream, `data` is JSON of this schema or a plain text line",
+                "content": {
+                    "text/event-stream": {
+                        "schema": schema.unwrap_or_else(|| json!({ "type": "string" })),
+                    },
+                },
+            }),
+        };
+        let mut operation = json!({
+            "summary": summary,
+            "parameters": parameters,
+            "responses": {
+                "200": success,
+                "4XX": {
+                    "description": "Error message",
+                    "content": { "text/plain": { "schema": { "type": "string" } } },
+                },
+            },
+        });
+        if let Some(schema) = body {
+            operation["requestBody"] = json!({
+                "required": true,
+                "content": { "application/json": { "schema": schema } },
+            });
+        }
+        let entry = self
+            .paths
+            .entry(openapi_path(path))
+            .or_insert_with(|| json!({}));
+        entry[method] = operation;
+    }
+}
+
+/// `/api/chains/:id` to `/api/chains/{id}`
+fn openapi_path(path: &str) -> String {
+    path.split('/')
+        .map(|segment| match segment.strip_prefix(':') {
+            Some(name) => format!("{{{}}}", name),
+            None => segment.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn path_params(path: &str) -> Vec<Value> {
+    path.split('/')
+        .filter_map(|segment| segment.strip_prefix(':'))
+        .map(|name| {
+            let schema = match name {
+                "hash" | "transactionhash" => json!({ "type": "string" }),
+                "paused" => json!({ "type": "boolean" }),
+                _ => json!({ "type": "integer", "format": "uint64", "minimum": 0 }),
+            };
+            json!({ "name": name, "in": "path", "required": true, "schema": schema })
+        })
+        .collect()
+}
+
+pub fn document() -> Value {
+    let mut spec = Spec {
+        generator: SchemaSettings::openapi3().into_generator(),
+        paths: Map::new(),
+    };
+
+    let reply = Reply::Json(spec.schema::<Health>());
+    spec.route(
+        "get",
+        "/api/health",
+        "Server status and settings",
+        vec![],
+        None,
+        reply,
+    );
+    spec.paths["/api/health"]["get"]["security"] = json!([]);
+    spec.route(
+        "get",
+        "/api/openapi.json",
+        "This document",
+        vec![],
+        None,
+        Reply::Json(json!({ "type": "object" })),
+    );
+    spec.paths["/api/openapi.json"]["get"]["security"] = json!([]);
+
+    // chains
+    let (query, reply) = (spec.query::<ListQuery>(), spec.schema::<Vec<ChainConfig>>());
+    spec.route(
+        "get",
+        "/api/chains",
+        "List chains, wrapped in a ChainsSummary with `summary=true`",
+        query,
+        None,
+        Reply::Json(reply),
+    );
+    let (body, reply) = (spec.schema::<ChainConfig>(), spec.schema::<ChainConfig>());
+    spec.route(
+        "post",
+        "/api/chains",
+        "Create a chain",
+        vec![],
+        Some(body),
+        Reply::Json(reply),
+    );
+    let (query, reply) = (
+        spec.query::<ExportQuery>(),
+        spec.schema::<Vec<ChainConfig>>(),
+    );
+    spec.route(
+        "get",
+        "/api/chains/export-all",
+        "Export every chain config",
+        query,
+        None,
+        Reply::Json(reply),
+    );
+    let (body, reply) = (
+        spec.schema::<Vec<ChainConfig>>(),
+        spec.schema::<Vec<ChainResult>>(),
+    );
+    spec.route(
+        "post",
+        "/api/chains/import-all",
+        "Create chains from an export, skipping existing ids",
+        vec![],
+        Some(body),
+        Reply::Json(reply),
+    );
+    let (query, reply) = (
+        spec.query::<AvailabilityQuery>(),
+        spec.schema::<Availability>(),
+    );
+    spec.route(
+        "get",
+        "/api/chains/available",
+        "Check whether a chain id and port are free",
+        query,
+        None,
+        Reply::Json(reply),
+    );
+    for (path, summary) in [
+        (
+            "/api/chains/start-all",
+            "Start every chain that isn't running",
+        ),
+        (
+            "/api/chains/stop-all",
+            "Stop every chain that isn't stopped",
+        ),
+        ("/api/chains/delete-all", "Stop and delete every chain"),
+        (
+            "/api/admin/shutdown",
+            "Stop every chain, then shut the server down",
+        ),
+    ] {
+        let reply = Reply::Json(spec.schema::<Vec<ChainResult>>());
+        spec.route("post", path, summary, vec![], None, reply);
+    }
+
+    // lifecycle
+    for (path, summary) in [
+        ("/api/chains/:id/start", "Start a chain"),
+        ("/api/chains/:id/stop", "Stop a chain"),
+        ("/api/chains/:id/delete", "Stop and remove a chain"),
+    ] {
+        spec.route("post", path, summary, vec![], None, Reply::Empty);
+    }
+    let query = spec.query::<RestartQuery>();
+    spec.route(
+        "post",
+        "/api/chains/:id/restart",
+        "Restart a chain",
+        query,
+        None,
+        Reply::Empty,
+    );
+    for (path, summary) in [
+        (
+            "/api/chains/:id/fork-local",
+            "Copy a running chain into a new one",
+        ),
+        ("/api/chains/:id/pin", "List the chain first"),
+        ("/api/chains/:id/unpin", "Undo pin"),
+        (
+            "/api/chains/:id/blocktime/:secs",
+            "Change a running chain's block time",
+        ),
+        (
+            "/api/chains/:id/gaslimit/:limit",
+            "Change a running chain's block gas limit",
+        ),
+    ] {
+        let reply = Reply::Json(spec.schema::<ChainConfig>());
+        spec.route("post", path, summary, vec![], None, reply);
+    }
+    let (body, reply) = (spec.schema::<RenameRequest>(), spec.schema::<ChainConfig>());
+    spec.route(
+        "post",
+        "/api/chains/:id/rename",
+        "Rename a chain",
+        vec![],
+        Some(body),
+        Reply::Json(reply),
+    );
+    let (body, reply) = (spec.schema::<NotesRequest>(), spec.schema::<ChainConfig>());
+    spec.route(
+        "post",
+        "/api/chains/:id/notes",
+        "Replace a chain's notes",
+        vec![],
+        Some(body),
+        Reply::Json(reply),
+    );
+
+    // mining and transactions
+    let query = spec.query::<MineQuery>();
+    spec.route(
+        "post",
+        "/api/chains/:id/mine",
+        "Mine blocks now",
+        query,
+        None,
+        Reply::Empty,
+    );
+    let (body, reply) = (
+        spec.schema::<MineUntilRequest>(),
+        spec.schema::<MineUntilResult>(),
+    );
+    spec.route(
+        "post",
+        "/api/chains/:id/mine-until",
+        "Mine until a block or a transaction's inclusion",
+        vec![],
+        Some(body),
+        Reply::Json(reply),
+    );
+    spec.route(
+        "post",
+        "/api/chains/:id/mining/:paused",
+        "Pause or resume mining",
+        vec![],
+        None,
+        Reply::Empty,
+    );
+    let reply = Reply::Json(spec.schema::<String>());
+    spec.route(
+        "post",
+        "/api/chains/:id/testtx",
+        "Send 1 wei between two prefunded accounts, answers the hash",
+        vec![],
+        None,
+        reply,
+    );
+    let reply = Reply::Json(spec.schema::<Recording>());
+    spec.route(
+        "get",
+        "/api/chains/:id/recording",
+        "Transactions recorded since the last start from genesis",
+        vec![],
+        None,
+        reply,
+    );
+    let (body, reply) = (spec.schema::<Recording>(), spec.schema::<ReplayResult>());
+    spec.route(
+        "post",
+        "/api/chains/:id/replay",
+        "Send a recording's transactions to this chain",
+        vec![],
+        Some(body),
+        Reply::Json(reply),
+    );
+    let call = spec.schema::<Value>();
+    spec.route(
+        "post",
+        "/api/chains/:id/rpc",
+        "Forward a JSON-RPC call or batch to anvil",
+        vec![],
+        Some(call.clone()),
+        Reply::Json(call),
+    );
+
+    // logs and streams
+    let query = spec.query::<LogStreamQuery>();
+    spec.route(
+        "get",
+        "/api/chains/:id/logstream",
+        "Live log lines after the buffered ones",
+        query,
+        None,
+        Reply::Events(None),
+    );
+    let (query, reply) = (
+        spec.query::<LogsSinceQuery>(),
+        spec.schema::<LogLinesResponse>(),
+    );
+    spec.route(
+        "get",
+        "/api/chains/:id/logs",
+        "Buffered log lines from an index on",
+        query,
+        None,
+        Reply::Json(reply),
+    );
+    spec.route(
+        "get",
+        "/api/chains/:id/logs/download",
+        "The whole log as a file",
+        vec![],
+        None,
+        Reply::Text,
+    );
+    let (query, reply) = (
+        spec.query::<LogSearchQuery>(),
+        spec.schema::<LogSearchResponse>(),
+    );
+    spec.route(
+        "get",
+        "/api/chains/:id/logs/search",
+        "Log lines containing a text",
+        query,
+        None,
+        Reply::Json(reply),
+    );
+    let reply = Reply::Events(Some(spec.schema::<Block>()));
+    spec.route(
+        "get",
+        "/api/chains/:id/blockstream",
+        "New blocks",
+        vec![],
+        None,
+        reply,
+    );
+    let reply = Reply::Events(Some(spec.schema::<ChainStatusEvent>()));
+    spec.route(
+        "get",
+        "/api/chains/:id/statusstream",
+        "Status changes and live stats",
+        vec![],
+        None,
+        reply,
+    );
+    let reply = Reply::Events(Some(spec.schema::<ChainEvent>()));
+    spec.route(
+        "get",
+        "/api/chains/events",
+        "Chains created, changed or deleted from now on",
+        vec![],
+        None,
+        reply,
+    );
+
+    // chain data
+    let reply = Reply::Json(spec.schema::<ResourceUsage>());
+    spec.route(
+        "get",
+        "/api/chains/:id/usage",
+        "Memory and CPU of anvil",
+        vec![],
+        None,
+        reply,
+    );
+    let reply = Reply::Json(spec.schema::<u64>());
+    spec.route(
+        "get",
+        "/api/chains/:id/head",
+        "Latest block number",
+        vec![],
+        None,
+        reply,
+    );
+    let reply = Reply::Json(spec.schema::<Vec<DevOnlyPrivateKey>>());
+    spec.route(
+        "get",
+        "/api/chains/:id/keys",
+        "Prefunded accounts and keys, with LOCALCHAIN_EXPOSE_KEYS",
+        vec![],
+        None,
+        reply,
+    );
+    let (query, reply) = (spec.query::<BlockRangeQuery>(), spec.schema::<Vec<Block>>());
+    spec.route(
+        "get",
+        "/api/chains/:id/blocks/range",
+        "Blocks `from` to `to`, newest first",
+        query,
+        None,
+        Reply::Json(reply),
+    );
+    spec.route(
+        "get",
+        "/api/chains/:id/tx/:hash/trace",
+        "Raw debug_traceTransaction result",
+        vec![],
+        None,
+        Reply::Json(json!({})),
+    );
+    let reply = Reply::Json(spec.schema::<BlockResponse>());
+    spec.route(
+        "get",
+        "/api/chains/:id/genesis",
+        "Block 0 and its transactions, cached until the chain restarts",
+        vec![],
+        None,
+        reply,
+    );
+    let (query, reply) = (spec.query::<FormatQuery>(), spec.schema::<BlockResponse>());
+    spec.route(
+        "get",
+        "/api/:chainid/blocks/:blocknumber",
+        "A block and its transactions, the JSON-RPC block with `format=rpc`",
+        query,
+        None,
+        Reply::Json(reply),
+    );
+    let (query, reply) = (
+        spec.query::<FormatQuery>(),
+        spec.schema::<TransactionResponse>(),
+    );
+    spec.route(
+        "get",
+        "/api/:chainid/transactions/:transactionhash",
+        "A transaction, the JSON-RPC transaction with `format=rpc`",
+        query,
+        None,
+        Reply::Json(reply),
+    );
+    let (query, reply) = (spec.query::<LogFilter>(), spec.schema::<LogsResponse>());
+    spec.route(
+        "get",
+        "/api/:chainid/events",
+        "Event logs matching a filter, paged",
+        query,
+        None,
+        Reply::Json(reply),
+    );
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "LocalChain API",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "paths": spec.paths,
+        "components": {
+            "schemas": spec.generator.take_definitions(true),
+            "securitySchemes": {
+                "token": { "type": "http", "scheme": "bearer" },
+            },
+        },
+        "security": [{ "token": [] }],
+    })
+}
@@ -0,0 +1,66 @@
+//! Method policy of the JSON-RPC proxy at `/api/chains/:id/rpc`.
+
+use serde_json::{json, Value};
+
+/// JSON-RPC error code for a request without a method
+pub const INVALID_REQUEST: i64 = -32600;
+/// JSON-RPC error code for methods the policy refuses, the same code a
+/// node uses for methods it doesn't have
+pub const METHOD_NOT_ALLOWED: i64 = -32601;
+/// JSON-RPC error code for the chain not answering at all
+pub const INTERNAL_ERROR: i64 = -32603;
+
+/// Which methods the proxy forwards, from `LOCALCHAIN_RPC_ALLOW` and
+/// `LOCALCHAIN_RPC_DENY`. Entries are method names, or a prefix ending in
+/// `*` such as `anvil_*`.
+#[derive(Debug, Default)]
+pub struct RpcPolicy {
+    /// Only these are forwarded when set
+    allow: Option<Vec<String>>,
+    /// Never forwarded, even when also allowed
+    deny: Vec<String>,
+}
+
+impl RpcPolicy {
+    pub fn from_env() -> Self {
+        RpcPolicy {
+            allow: std::env::var("LOCALCHAIN_RPC_ALLOW")
+                .ok()
+                .map(|v| split_list(&v))
+                .filter(|list| !list.is_empty()),
+            deny: std::env::var("LOCALCHAIN_RPC_DENY")
+                .map(|v| split_list(&v))
+                .unwrap_or_default(),
+        }
+    }
+
+    pub fn allows(&self, method: &str) -> bool {
+        let matches = |patterns: &[String]| patterns.iter().any(|p| matches(p, method));
+        !matches(&self.deny) && self.allow.as_deref().is_none_or(matches)
+    }
+}
+
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|m| !m.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+fn matches(pattern: &str, method: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => method.starts_with(prefix),
+        None => pattern == method,
+    }
+}
+
+/// Response to a call that wasn't forwarded
+pub fn error_response(id: Value, code: i64, message: impl Into<String>) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message.into() },
+    })
+}
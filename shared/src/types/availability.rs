@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+/// `GET /api/chains/available?id=&port=`, `None` for a value that wasn't
+/// asked about
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Availability {
+    /// No chain uses the id
+    pub id_available: Option<bool>,
+    /// No chain uses the port and it can be bound right now
+    pub port_available: Option<bool>,
+}
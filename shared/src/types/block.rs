@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Block {
     pub beneficiary: String,
     pub gas_limit: u64,
@@ -10,6 +11,8 @@ pub struct Block {
     pub time: u64,
     pub nonce: String,
     pub transactions: u64,
+    /// Mined by this anvil, false for history inherited from a fork
+    pub is_local: bool,
 }
 
 impl Block {
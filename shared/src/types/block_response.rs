@@ -2,7 +2,11 @@ use crate::types::block::Block;
 use crate::types::transaction::Transaction;
 use serde::{Deserialize, Serialize};
 
+/// Default shape of `GET /api/:chainid/blocks/:blocknumber`, trimmed to what
+/// the explorer shows. With `?format=rpc` the endpoint returns the node's
+/// `eth_getBlockByNumber` object with full transactions instead.
 #[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct BlockResponse {
     pub block: Block,
     pub transactions: Vec<Transaction>,
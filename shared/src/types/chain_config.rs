@@ -1,27 +1,335 @@
+use crate::redact::{is_redacted, redact_url};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ChainConfig {
     /// Nice name for UI display only
     pub name: String,
     /// Chain Identifier, must be unique, used in API
     pub id: u64,
     pub port: u16,
+    /// Interface anvil listens on, `127.0.0.1` when unset
+    #[serde(default)]
+    pub host: Option<String>,
+    /// Seconds between blocks, 0 mines a block per transaction
+    #[serde(default)]
     pub block_time: u64,
+    #[serde(default)]
     pub status: ChainStatus,
     pub fork_url: Option<String>,
+    /// Upstream block to fork at, anvil's `--fork-block-number`. The
+    /// upstream head when unset.
+    #[serde(default)]
+    pub fork_block_number: Option<u64>,
+    /// Without a `fork_block_number`, fork at the upstream head seen on the
+    /// first start and reuse that block on every restart
+    #[serde(default)]
+    pub pin_fork_block: bool,
+    /// With a `block_time` of 0, mine an empty block this often through
+    /// `anvil_mine` so timestamps keep moving
+    #[serde(default)]
+    pub heartbeat_secs: Option<u64>,
+    /// Blocks mined right after start so the chain doesn't begin at height 0
+    #[serde(default)]
+    pub premine_blocks: Option<u64>,
+    /// CSS color used as the column accent in the UI
+    #[serde(default)]
+    pub color: Option<String>,
+    /// Free-form labels for grouping chains in the UI
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Free-form annotation shown in the Info panel, not passed to anvil
+    #[serde(default)]
+    pub notes: Option<String>,
+    /// Unix time of the genesis block, anvil's `--timestamp`
+    #[serde(default)]
+    pub genesis_timestamp: Option<u64>,
+    /// Gas price in wei, anvil's `--gas-price`
+    #[serde(default)]
+    pub gas_price: Option<u64>,
+    /// Gas limit of every block, anvil's `--gas-limit`
+    #[serde(default)]
+    pub block_gas_limit: Option<u64>,
+    /// Native currency symbol the UI shows values in, `ETH` when unset
+    #[serde(default)]
+    pub symbol: Option<String>,
+    /// Native currency decimals the UI scales values by, 18 when unset
+    #[serde(default)]
+    pub decimals: Option<u8>,
+    /// Run anvil with `--steps-tracing` so transactions can be traced opcode by opcode
+    #[serde(default)]
+    pub tracing: bool,
+    /// Keep the signed form of every mined transaction so it can be
+    /// exported from `/api/chains/:id/recording` and replayed elsewhere
+    #[serde(default)]
+    pub record_transactions: bool,
+    /// Extra environment variables set on the anvil process, e.g. `RUST_LOG`
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    /// Passed to anvil verbatim after the managed flags
+    #[serde(default)]
+    pub extra_args: Vec<String>,
+    /// Log lines kept in memory for this chain, the server's
+    /// `LOCALCHAIN_LOG_BUFFER` when unset
+    #[serde(default)]
+    pub log_retention: Option<usize>,
+    /// Where anvil runs, the local binary when unset
+    #[serde(default)]
+    pub runtime: Option<Runtime>,
+    /// Chain id anvil reports over `eth_chainId` while running, filled in by
+    /// the server and ignored on create
+    #[serde(default)]
+    pub actual_chain_id: Option<u64>,
+    /// Unix time the running chain was started, filled in by the server and
+    /// ignored on create
+    #[serde(default)]
+    pub started_at: Option<u64>,
+    /// Upstream block `pin_fork_block` resolved to, filled in by the server
+    /// and kept until it restarts
+    #[serde(default)]
+    pub resolved_fork_block: Option<u64>,
+    /// Started when the server boots and reloads it from the state file
+    #[serde(default)]
+    pub auto_start: bool,
+    /// Listed before unpinned chains in the UI
+    #[serde(default)]
+    pub pinned: bool,
+    /// Assigned by the server on create, chains are listed in this order
+    #[serde(default)]
+    pub created_seq: u64,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum ChainStatus {
+    #[default]
     Stopped,
     Running,
     Starting,
     Error,
 }
 
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum Runtime {
+    /// `anvil` from the server's `PATH`
+    #[default]
+    Native,
+    /// `anvil` inside the Foundry Docker image, for machines without a
+    /// Foundry install
+    Docker,
+}
+
+pub const DEFAULT_HOST: &str = "127.0.0.1";
+pub const DEFAULT_SYMBOL: &str = "ETH";
+pub const DEFAULT_DECIMALS: u8 = 18;
+
+/// `wei` as an amount of the native currency, e.g. `1500000000000000000`
+/// with 18 decimals is `1.5 ETH`. Anything but a decimal integer is returned
+/// as is.
+pub fn format_value(wei: &str, decimals: u8, symbol: &str) -> String {
+    if wei.is_empty() || !wei.bytes().all(|b| b.is_ascii_digit()) {
+        return wei.to_string();
+    }
+    let decimals = decimals as usize;
+    let digits = format!(
+        "{:0>width$}",
+        wei.trim_start_matches('0'),
+        width = decimals + 1
+    );
+    let (whole, fraction) = digits.split_at(digits.len() - decimals);
+    match fraction.trim_end_matches('0') {
+        "" => format!("{} {}", whole, symbol),
+        fraction => format!("{}.{} {}", whole, fraction, symbol),
+    }
+}
+
+/// Latest genesis timestamp accepted, 9999-12-31T23:59:59Z
+pub const MAX_GENESIS_TIMESTAMP: u64 = 253_402_300_799;
+
+/// Largest chain id that still fits an EIP-155 `v` value in a u64 (EIP-2294)
+pub const MAX_CHAIN_ID: u64 = u64::MAX / 2 - 36;
+
+/// Most blocks `premine_blocks` may ask for
+pub const MAX_PREMINE_BLOCKS: u64 = 100_000;
+
+/// Lowest block gas limit accepted, the cost of a plain transfer
+pub const MIN_BLOCK_GAS_LIMIT: u64 = 21_000;
+
+/// Bounds of `log_retention`, a few lines are useless and the upper bound
+/// keeps one chain from taking the server's memory
+pub const MIN_LOG_RETENTION: usize = 100;
+pub const MAX_LOG_RETENTION: usize = 1_000_000;
+
+/// Ports below this need root to bind on most systems
+pub const MIN_UNPRIVILEGED_PORT: u16 = 1024;
+/// `port` value asking the server to pick a free port when the chain is
+/// created. The stored config always has the concrete port.
+pub const AUTO_PORT: u16 = 0;
+
 impl ChainConfig {
-    pub fn next(existing: &Vec<ChainConfig>) -> ChainConfig {
+    /// Checks the fields that don't depend on other chains. Ports below
+    /// `MIN_UNPRIVILEGED_PORT` are only accepted with `allow_privileged_ports`,
+    /// except `AUTO_PORT`.
+    pub fn validate(&self, allow_privileged_ports: bool) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("Name is required".to_string());
+        }
+        if !self
+            .name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err("Name must be alphanumeric (dash/underscore allowed)".to_string());
+        }
+
+        if self.id == 0 {
+            return Err("Chain ID must be greater than 0".to_string());
+        }
+        if self.id > MAX_CHAIN_ID {
+            return Err(format!("Chain ID must be at most {}", MAX_CHAIN_ID));
+        }
+
+        if self.port != AUTO_PORT && self.port < MIN_UNPRIVILEGED_PORT && !allow_privileged_ports {
+            return Err(format!(
+                "Port must be at least {}, lower ports need root",
+                MIN_UNPRIVILEGED_PORT
+            ));
+        }
+
+        if let Some(host) = &self.host {
+            host.parse::<IpAddr>()
+                .map_err(|_| format!("Host must be an IP address, got {:?}", host))?;
+        }
+
+        if let Some(ts) = self.genesis_timestamp {
+            // anything this large was almost certainly given in milliseconds
+            if ts == 0 || ts > MAX_GENESIS_TIMESTAMP {
+                return Err("Genesis timestamp must be a Unix time in seconds".to_string());
+            }
+        }
+
+        if let Some(heartbeat) = self.heartbeat_secs {
+            if heartbeat == 0 {
+                return Err("Heartbeat must be greater than 0".to_string());
+            }
+            if self.block_time != 0 {
+                return Err("Heartbeat needs a block time of 0".to_string());
+            }
+        }
+
+        if self.premine_blocks.is_some_and(|n| n > MAX_PREMINE_BLOCKS) {
+            return Err(format!(
+                "Pre-mined blocks must be at most {}",
+                MAX_PREMINE_BLOCKS
+            ));
+        }
+
+        if self.fork_url.as_deref().is_some_and(is_redacted) {
+            return Err("Fork URL has a masked API key, enter the full URL".to_string());
+        }
+        let forked = self
+            .fork_url
+            .as_deref()
+            .is_some_and(|u| !u.trim().is_empty());
+        if self.fork_block_number.is_some() && !forked {
+            return Err("Fork block needs a fork URL".to_string());
+        }
+
+        if let Some(color) = &self.color {
+            let is_hex_color = color.len() == 7
+                && color.starts_with('#')
+                && color[1..].chars().all(|c| c.is_ascii_hexdigit());
+            if !is_hex_color {
+                return Err("Color must be a hex value like #3b82f6".to_string());
+            }
+        }
+
+        if let Some(symbol) = &self.symbol {
+            // wallets reject symbols longer than 11 characters (EIP-747)
+            if symbol.is_empty()
+                || symbol.len() > 11
+                || !symbol.chars().all(|c| c.is_ascii_alphanumeric())
+            {
+                return Err("Symbol must be 1-11 alphanumeric characters".to_string());
+            }
+        }
+        for key in self.env.keys() {
+            let mut chars = key.chars();
+            let is_env_name = chars
+                .next()
+                .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if !is_env_name {
+                return Err(format!("Invalid environment variable name: {:?}", key));
+            }
+        }
+        if self
+            .block_gas_limit
+            .is_some_and(|limit| limit < MIN_BLOCK_GAS_LIMIT)
+        {
+            return Err(format!(
+                "Block gas limit must be at least {}",
+                MIN_BLOCK_GAS_LIMIT
+            ));
+        }
+        if self.decimals.is_some_and(|d| d > 36) {
+            return Err("Decimals must be at most 36".to_string());
+        }
+        if self
+            .log_retention
+            .is_some_and(|n| !(MIN_LOG_RETENTION..=MAX_LOG_RETENTION).contains(&n))
+        {
+            return Err(format!(
+                "Log retention must be between {} and {} lines",
+                MIN_LOG_RETENTION, MAX_LOG_RETENTION
+            ));
+        }
+        Ok(())
+    }
+
+    /// Copy that is safe to show or export, with the fork URL's key masked
+    pub fn redacted(&self) -> ChainConfig {
+        ChainConfig {
+            fork_url: self.fork_url.as_deref().map(redact_url),
+            ..self.clone()
+        }
+    }
+
+    pub fn host(&self) -> &str {
+        self.host.as_deref().unwrap_or(DEFAULT_HOST)
+    }
+
+    /// HTTP JSON-RPC endpoint, e.g. for wallets or scripts
+    pub fn rpc_url(&self) -> String {
+        match self.host().parse::<IpAddr>() {
+            Ok(ip) => format!("http://{}", SocketAddr::new(ip, self.port)),
+            Err(_) => format!("http://{}:{}", self.host(), self.port),
+        }
+    }
+
+    pub fn runtime(&self) -> Runtime {
+        self.runtime.unwrap_or_default()
+    }
+
+    pub fn symbol(&self) -> &str {
+        self.symbol.as_deref().unwrap_or(DEFAULT_SYMBOL)
+    }
+
+    pub fn decimals(&self) -> u8 {
+        self.decimals.unwrap_or(DEFAULT_DECIMALS)
+    }
+
+    /// `format_value` in this chain's currency
+    pub fn format_value(&self, wei: &str) -> String {
+        format_value(wei, self.decimals(), self.symbol())
+    }
+
+    pub fn next(existing: &[ChainConfig]) -> ChainConfig {
         ChainConfig {
             name: format!("Chain-{}", existing.len() + 1),
             id: existing.iter().map(|c| c.id).max().unwrap_or(0) + 1,
@@ -29,6 +337,67 @@ impl ChainConfig {
             block_time: 1,
             status: ChainStatus::Stopped,
             fork_url: None,
+            ..Default::default()
         }
     }
 }
+
+/// Splits a command line on whitespace, keeping single- or double-quoted
+/// sections together and honouring backslash escapes outside single quotes
+pub fn split_args(input: &str) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    let mut current: Option<String> = None;
+    let mut quote: Option<char> = None;
+    let mut chars = input.chars();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                current.get_or_insert_with(String::new);
+            }
+            (None | Some('"'), '\\') => {
+                let escaped = chars.next().ok_or("Trailing backslash")?;
+                current.get_or_insert_with(String::new).push(escaped);
+            }
+            (None, c) if c.is_whitespace() => args.extend(current.take()),
+            (_, c) => current.get_or_insert_with(String::new).push(c),
+        }
+    }
+    if quote.is_some() {
+        return Err("Unterminated quote".to_string());
+    }
+    args.extend(current);
+    Ok(args)
+}
+
+/// Inverse of `split_args`, quoting arguments that need it
+pub fn join_args<S: AsRef<str>>(args: &[S]) -> String {
+    args.iter()
+        .map(|arg| {
+            let arg = arg.as_ref();
+            if !arg.is_empty() && !arg.contains(|c: char| c.is_whitespace() || "'\"\\".contains(c))
+            {
+                arg.to_string()
+            } else {
+                format!("'{}'", arg.replace('\'', "'\"'\"'"))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_values_in_the_native_currency() {
+        assert_eq!(format_value("1500000000000000000", 18, "ETH"), "1.5 ETH");
+        assert_eq!(format_value("1", 18, "ETH"), "0.000000000000000001 ETH");
+        assert_eq!(format_value("0", 18, "ETH"), "0 ETH");
+        assert_eq!(format_value("2500", 3, "TST"), "2.5 TST");
+        assert_eq!(format_value("42", 0, "TST"), "42 TST");
+        assert_eq!(format_value("0x10", 18, "ETH"), "0x10");
+    }
+}
@@ -0,0 +1,22 @@
+use crate::types::chain_config::ChainConfig;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum ChainEventKind {
+    Created,
+    Updated,
+    Deleted,
+}
+
+/// Sent on `/api/chains/events` when a chain is created, its config changes
+/// or it is deleted, so every open tab can keep its list current
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ChainEvent {
+    pub kind: ChainEventKind,
+    /// The config after the change, or the last one for `deleted`, with the
+    /// fork URL's key masked
+    pub chain: ChainConfig,
+}
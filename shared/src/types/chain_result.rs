@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+/// Outcome for one chain of a bulk operation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ChainResult {
+    pub id: u64,
+    pub name: String,
+    pub ok: bool,
+    pub error: Option<String>,
+}
@@ -0,0 +1,22 @@
+use crate::types::chain_config::ChainStatus;
+use serde::{Deserialize, Serialize};
+
+/// Sent on `/api/chains/:id/statusstream` when the status changes and
+/// periodically in between
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ChainStatusEvent {
+    pub status: ChainStatus,
+    /// Highest block seen since the chain started
+    pub latest_block: Option<u64>,
+    /// Transactions mined since the chain started
+    pub transactions: u64,
+    /// False while the server's WebSocket to anvil is down and reconnecting
+    pub rpc_connected: bool,
+    /// Set while mining is paused and blocks only come from `/mine`
+    #[serde(default)]
+    pub mining_paused: bool,
+    /// False when anvil only answers over HTTP, blocks aren't streamed then
+    #[serde(default)]
+    pub streaming_available: bool,
+}
@@ -0,0 +1,28 @@
+use crate::types::chain_config::{ChainConfig, ChainStatus};
+use serde::{Deserialize, Serialize};
+
+/// `GET /api/chains?summary=true`, the chain list with counts per status
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ChainsSummary {
+    pub chains: Vec<ChainConfig>,
+    pub total: usize,
+    pub running: usize,
+    pub starting: usize,
+    pub stopped: usize,
+    pub error: usize,
+}
+
+impl ChainsSummary {
+    pub fn new(chains: Vec<ChainConfig>) -> Self {
+        let count = |status: ChainStatus| chains.iter().filter(|c| c.status == status).count();
+        ChainsSummary {
+            total: chains.len(),
+            running: count(ChainStatus::Running),
+            starting: count(ChainStatus::Starting),
+            stopped: count(ChainStatus::Stopped),
+            error: count(ChainStatus::Error),
+            chains,
+        }
+    }
+}
@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+/// A prefunded anvil account with its private key, returned by
+/// `/api/chains/:id/keys` only when `LOCALCHAIN_EXPOSE_KEYS` is set.
+/// These keys are public knowledge for default mnemonics, never fund them
+/// on a real network.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DevOnlyPrivateKey {
+    pub index: u64,
+    pub address: String,
+    pub private_key: String,
+}
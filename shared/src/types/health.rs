@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+/// Server status and capabilities, returned by `/api/health`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Health {
+    pub status: String,
+    /// Mutating routes are rejected with 403
+    pub read_only: bool,
+    /// Chains may use ports below 1024
+    #[serde(default)]
+    pub allow_privileged_ports: bool,
+    /// Creating chains beyond this many is refused, unlimited when `None`
+    #[serde(default)]
+    pub max_chains: Option<usize>,
+    /// The server found the web client's `index.html`
+    #[serde(default)]
+    pub client_built: bool,
+    /// Version of the anvil binary found at startup, `None` when it couldn't
+    /// be run
+    #[serde(default)]
+    pub anvil_version: Option<String>,
+}
@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+
+/// Query parameters for the events endpoint
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct LogFilter {
+    /// Contract address emitting the events
+    pub address: Option<String>,
+    /// Event signature hash
+    pub topic0: Option<String>,
+    pub topic1: Option<String>,
+    pub topic2: Option<String>,
+    pub topic3: Option<String>,
+    /// Defaults to the genesis block
+    pub from_block: Option<u64>,
+    /// Defaults to the latest block
+    pub to_block: Option<u64>,
+    /// Zero based page index
+    pub page: Option<u64>,
+    pub page_size: Option<u64>,
+}
+
+impl LogFilter {
+    pub const DEFAULT_PAGE_SIZE: u64 = 25;
+    pub const MAX_PAGE_SIZE: u64 = 100;
+
+    pub fn topics(&self) -> [&Option<String>; 4] {
+        [&self.topic0, &self.topic1, &self.topic2, &self.topic3]
+    }
+
+    pub fn page(&self) -> u64 {
+        self.page.unwrap_or(0)
+    }
+
+    pub fn page_size(&self) -> u64 {
+        self.page_size
+            .unwrap_or(Self::DEFAULT_PAGE_SIZE)
+            .clamp(1, Self::MAX_PAGE_SIZE)
+    }
+
+    /// Encodes the non empty fields as `key=value` pairs joined by `&`
+    pub fn to_query(&self) -> String {
+        let mut pairs = vec![];
+        let text_fields = [
+            ("address", &self.address),
+            ("topic0", &self.topic0),
+            ("topic1", &self.topic1),
+            ("topic2", &self.topic2),
+            ("topic3", &self.topic3),
+        ];
+        for (key, value) in text_fields {
+            if let Some(value) = value.as_ref().filter(|v| !v.trim().is_empty()) {
+                pairs.push(format!("{}={}", key, value.trim()));
+            }
+        }
+        let number_fields = [
+            ("from_block", self.from_block),
+            ("to_block", self.to_block),
+            ("page", self.page),
+            ("page_size", self.page_size),
+        ];
+        for (key, value) in number_fields {
+            if let Some(value) = value {
+                pairs.push(format!("{}={}", key, value));
+            }
+        }
+        pairs.join("&")
+    }
+}
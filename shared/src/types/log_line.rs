@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// One line of a chain's log, sent as-is by
+/// `GET /api/chains/:id/logstream?format=json`:
+///
+/// ```json
+/// { "ts": 1718000000000, "stream": "stdout", "chain_id": 1, "text": "Listening on 127.0.0.1:8545" }
+/// ```
+///
+/// `ts` is milliseconds since the Unix epoch, `stream` is one of `stdout`,
+/// `stderr` (anvil output) or `manager` (lifecycle messages from the server).
+/// The default plain format renders the line as `[stream] text`, see
+/// `LogLine::to_plain` for other prefixes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct LogLine {
+    pub ts: u64,
+    pub stream: LogStream,
+    pub chain_id: u64,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum LogStream {
+    Stdout,
+    Stderr,
+    Manager,
+}
+
+impl fmt::Display for LogStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LogStream::Stdout => write!(f, "stdout"),
+            LogStream::Stderr => write!(f, "stderr"),
+            LogStream::Manager => write!(f, "manager"),
+        }
+    }
+}
+
+/// Prefix of the plain format, see `LogLine::to_plain`
+pub const DEFAULT_PREFIX: &str = "[{stream}] ";
+
+impl fmt::Display for LogLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_plain(DEFAULT_PREFIX))
+    }
+}
+
+impl LogLine {
+    /// Renders the line as `prefix` followed by the text. The prefix may
+    /// contain `{stream}`, `{chain_id}`, `{ts}` (epoch milliseconds) and
+    /// `{time}` (UTC `HH:MM:SS.mmm`).
+    pub fn to_plain(&self, prefix: &str) -> String {
+        let ms = self.ts % 86_400_000;
+        let time = format!(
+            "{:02}:{:02}:{:02}.{:03}",
+            ms / 3_600_000,
+            ms / 60_000 % 60,
+            ms / 1000 % 60,
+            ms % 1000
+        );
+        let prefix = prefix
+            .replace("{stream}", &self.stream.to_string())
+            .replace("{chain_id}", &self.chain_id.to_string())
+            .replace("{ts}", &self.ts.to_string())
+            .replace("{time}", &time);
+        format!("{}{}", prefix, self.text)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+}
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+/// Buffered log lines from an index on, see
+/// `GET /api/chains/:id/logs?since=<index>&limit=<n>`
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct LogLinesResponse {
+    /// Lines in the plain format of the log stream, oldest first
+    pub lines: Vec<String>,
+    /// Index after the last line returned, the `since` of the next fetch
+    pub next_index: u64,
+    /// Lines from `since` on that fell out of the buffer before this fetch
+    /// and were skipped
+    pub dropped: u64,
+}
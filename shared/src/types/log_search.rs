@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+/// A log line containing the searched text
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct LogMatch {
+    /// Index of the line since the chain was created, as taken by
+    /// `GET /api/chains/:id/logs?since=`
+    pub index: u64,
+    pub line: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct LogSearchResponse {
+    /// The most recent matches, oldest first
+    pub matches: Vec<LogMatch>,
+    /// Number of matching lines, including those cut off by the limit
+    pub total: u64,
+}
+
+impl LogSearchResponse {
+    pub const DEFAULT_LIMIT: usize = 100;
+    pub const MAX_LIMIT: usize = 1000;
+}
@@ -0,0 +1,13 @@
+use crate::types::log::Log;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct LogsResponse {
+    /// Logs on the requested page, newest first
+    pub logs: Vec<Log>,
+    /// Number of logs matching the filter across all pages
+    pub total: u64,
+    pub page: u64,
+    pub page_size: u64,
+}
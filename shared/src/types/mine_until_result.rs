@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+/// Outcome of `POST /api/chains/:id/mine-until`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct MineUntilResult {
+    /// Blocks mined by this call, not counting ones anvil mined on its own
+    pub mined: u64,
+    /// Whether the condition held when mining stopped, false when the block
+    /// cap was hit first
+    pub reached: bool,
+    /// Head once mining stopped
+    pub block_number: u64,
+}
@@ -1,5 +1,22 @@
+pub mod availability;
 pub mod block;
 pub mod block_response;
 pub mod chain_config;
+pub mod chain_event;
+pub mod chain_result;
+pub mod chain_status_event;
+pub mod chains_summary;
+pub mod dev_only_private_key;
+pub mod health;
+pub mod log;
+pub mod log_filter;
+pub mod log_line;
+pub mod log_lines;
+pub mod log_search;
+pub mod logs_response;
+pub mod mine_until_result;
+pub mod recording;
+pub mod replay_result;
+pub mod resource_usage;
 pub mod transaction;
 pub mod transaction_response;
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+/// A mined transaction in its signed form
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RecordedTransaction {
+    pub block_number: u64,
+    pub hash: String,
+    /// RLP encoded signed transaction as `0x` hex, as returned by
+    /// `eth_getRawTransactionByHash`
+    pub raw: String,
+}
+
+/// Transactions a chain mined since its last start from genesis, oldest
+/// first. Signatures bind them to `chain_id`, so they only replay on a
+/// chain with the same id and accounts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Recording {
+    pub chain_id: u64,
+    pub transactions: Vec<RecordedTransaction>,
+}
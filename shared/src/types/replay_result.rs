@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+/// Outcome of replaying a `Recording`, which stops at the first transaction
+/// the target rejects
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ReplayResult {
+    /// Hashes of the transactions sent, in order
+    pub sent: Vec<String>,
+    pub error: Option<String>,
+}
@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+/// Memory and CPU used by a chain's anvil process, returned by
+/// `/api/chains/:id/usage`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ResourceUsage {
+    pub pid: u32,
+    /// Resident set size
+    pub rss_bytes: u64,
+    /// User plus system CPU time since the process started
+    pub cpu_time_ms: u64,
+}
@@ -1,9 +1,26 @@
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Transaction {
     pub hash: String,
-    pub block_number: u64,
-    pub index: u64,
+    /// `None` while the transaction is pending
+    #[serde(default)]
+    pub block_number: Option<u64>,
+    /// Position in its block, `None` while pending
+    #[serde(default)]
+    pub index: Option<u64>,
     pub from: String,
+    /// `None` for contract creations
+    #[serde(default)]
+    pub to: Option<String>,
+    /// Wei as a decimal string, it doesn't fit a u64
+    #[serde(default)]
+    pub value: String,
+    /// Gas limit
+    #[serde(default)]
+    pub gas: u64,
+    /// Calldata as 0x-prefixed hex
+    #[serde(default)]
+    pub input: String,
 }
@@ -1,7 +1,14 @@
 use crate::types::transaction::Transaction;
 use serde::{Deserialize, Serialize};
 
+/// Default shape of `GET /api/:chainid/transactions/:transactionhash`. With
+/// `?format=rpc` the endpoint returns the node's `eth_getTransactionByHash`
+/// object instead.
 #[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct TransactionResponse {
     pub transaction: Transaction,
+    /// Blocks from the transaction's block to the head, both included, so
+    /// 1 while its block is the latest and 0 while it is pending
+    pub confirmations: u64,
 }